@@ -0,0 +1,274 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The width of the registers of a bus-backed device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Word {
+    U8,
+    U16,
+    U32,
+}
+
+impl Word {
+    pub fn bits(&self) -> u8 {
+        match self {
+            Self::U8 => 8,
+            Self::U16 => 16,
+            Self::U32 => 32,
+        }
+    }
+
+    pub fn bytes(&self) -> usize {
+        usize::from(self.bits() / 8)
+    }
+
+    fn ty(&self) -> TokenStream {
+        match self {
+            Self::U8 => quote! { u8 },
+            Self::U16 => quote! { u16 },
+            Self::U32 => quote! { u32 },
+        }
+    }
+}
+
+/// The byte order of multi-byte registers on the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+/// A bus (such as I²C or SPI) through which the registers of an external device are accessed.
+///
+/// Peripheral base addresses and register offsets are interpreted as device register addresses
+/// and every access is performed through a user-supplied implementor of `proto_hal::bus::Bus`
+/// (or `proto_hal::bus::AsyncBus`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bus {
+    pub word: Word,
+    pub endianness: Endianness,
+    pub asynchronous: bool,
+}
+
+impl Bus {
+    pub fn new(word: Word) -> Self {
+        Self {
+            word,
+            endianness: Endianness::Big,
+            asynchronous: false,
+        }
+    }
+
+    pub fn endianness(self, endianness: Endianness) -> Self {
+        Self { endianness, ..self }
+    }
+
+    pub fn asynchronous(self) -> Self {
+        Self {
+            asynchronous: true,
+            ..self
+        }
+    }
+}
+
+/// The shape of the generated register access interface.
+///
+/// Memory-mapped interfaces are infallible and take no arguments, while bus-backed
+/// interfaces are generic over the bus, accept it as the first argument, and
+/// are fallible (and optionally asynchronous).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Interface<'a> {
+    bus: Option<&'a Bus>,
+}
+
+impl<'a> Interface<'a> {
+    pub fn new(bus: Option<&'a Bus>) -> Self {
+        Self { bus }
+    }
+
+    pub fn bus(&self) -> Option<&'a Bus> {
+        self.bus
+    }
+
+    /// The generic bus parameter (with trailing comma).
+    pub fn generic(&self) -> Option<TokenStream> {
+        self.bus.map(|bus| {
+            if bus.asynchronous {
+                quote! { _Bus: ::proto_hal::bus::AsyncBus, }
+            } else {
+                quote! { _Bus: ::proto_hal::bus::Bus, }
+            }
+        })
+    }
+
+    /// The bus argument in a function signature (with trailing comma).
+    pub fn param(&self) -> Option<TokenStream> {
+        self.bus.map(|_| quote! { bus: &mut _Bus, })
+    }
+
+    /// The bus argument in a function call (with trailing comma).
+    pub fn arg(&self) -> Option<TokenStream> {
+        self.bus.map(|_| quote! { bus, })
+    }
+
+    pub fn asyncness(&self) -> Option<TokenStream> {
+        self.bus
+            .filter(|bus| bus.asynchronous)
+            .map(|_| quote! { async })
+    }
+
+    /// Complete a call to another function of the interface, propagating errors.
+    pub fn complete(&self) -> Option<TokenStream> {
+        self.bus.map(|bus| {
+            if bus.asynchronous {
+                quote! { .await? }
+            } else {
+                quote! { ? }
+            }
+        })
+    }
+
+    /// The return type of a function returning `ty`.
+    pub fn ret(&self, ty: Option<TokenStream>) -> Option<TokenStream> {
+        match self.bus {
+            Some(..) => {
+                let ty = ty.unwrap_or(quote! { () });
+                Some(quote! { -> ::core::result::Result<#ty, _Bus::Error> })
+            }
+            None => ty.map(|ty| quote! { -> #ty }),
+        }
+    }
+
+    /// The return expression of a function returning `expr`.
+    pub fn ok(&self, expr: Option<TokenStream>) -> Option<TokenStream> {
+        match self.bus {
+            Some(..) => {
+                let expr = expr.unwrap_or(quote! { () });
+                Some(quote! { ::core::result::Result::Ok(#expr) })
+            }
+            None => expr,
+        }
+    }
+
+    /// Functions performing raw register access.
+    ///
+    /// Writes to shadowed registers are recorded in the register shadow.
+    pub fn raw_access(&self, register: &str, shadowed: bool) -> TokenStream {
+        let shadow = shadowed.then(|| {
+            quote! {
+                SHADOW.store(value, ::core::sync::atomic::Ordering::Relaxed);
+            }
+        });
+
+        let Some(bus) = self.bus else {
+            return quote! {
+                #[allow(unused)]
+                #[inline(always)]
+                unsafe fn read_raw(via_modify_in_cs: bool) -> u32 {
+                    let value = 'read: {
+                        #[cfg(feature = "sim")]
+                        if let Some(value) = ::proto_hal::sim::read(super::IDENT, OFFSET as _) {
+                            break 'read value;
+                        }
+
+                        unsafe { ::core::ptr::read_volatile((super::base_addr() + OFFSET) as *const u32) }
+                    };
+
+                    #[cfg(feature = "trace")]
+                    ::proto_hal::trace::record(::proto_hal::trace::Access {
+                        peripheral: super::IDENT,
+                        register: #register,
+                        kind: ::proto_hal::trace::Kind::Read,
+                        value,
+                        via_modify_in_cs,
+                    });
+
+                    value
+                }
+
+                #[allow(unused)]
+                #[inline(always)]
+                unsafe fn write_raw(value: u32, via_modify_in_cs: bool) {
+                    #shadow
+
+                    #[cfg(feature = "trace")]
+                    ::proto_hal::trace::record(::proto_hal::trace::Access {
+                        peripheral: super::IDENT,
+                        register: #register,
+                        kind: ::proto_hal::trace::Kind::Write,
+                        value,
+                        via_modify_in_cs,
+                    });
+
+                    #[cfg(feature = "sim")]
+                    if ::proto_hal::sim::write(super::IDENT, OFFSET as _, value) {
+                        return;
+                    }
+
+                    unsafe { ::core::ptr::write_volatile((super::base_addr() + OFFSET) as *mut u32, value) };
+                }
+            };
+        };
+
+        let generic = self.generic();
+        let asyncness = self.asyncness();
+        let complete = self.complete();
+
+        let bytes = bus.word.bytes();
+        let ty = bus.word.ty();
+        let (from_bytes, to_bytes) = match bus.endianness {
+            Endianness::Little => (quote! { from_le_bytes }, quote! { to_le_bytes }),
+            Endianness::Big => (quote! { from_be_bytes }, quote! { to_be_bytes }),
+        };
+        let (widen, narrow) = match bus.word {
+            Word::U32 => (quote! { value }, quote! { value }),
+            _ => (quote! { u32::from(value) }, quote! { value as #ty }),
+        };
+
+        quote! {
+            #[allow(unused)]
+            #asyncness unsafe fn read_raw<#generic>(bus: &mut _Bus) -> ::core::result::Result<u32, _Bus::Error> {
+                let mut bytes = [0u8; #bytes];
+                bus.read((super::base_addr() + OFFSET) as u32, &mut bytes)#complete;
+
+                let value = #ty::#from_bytes(bytes);
+
+                ::core::result::Result::Ok(#widen)
+            }
+
+            #[allow(unused)]
+            #asyncness unsafe fn write_raw<#generic>(bus: &mut _Bus, value: u32) -> ::core::result::Result<(), _Bus::Error> {
+                bus.write((super::base_addr() + OFFSET) as u32, &(#narrow).#to_bytes())#complete;
+                #shadow
+
+                ::core::result::Result::Ok(())
+            }
+        }
+    }
+
+    /// An expression reading the raw register value.
+    ///
+    /// Traced accesses are flagged with `via_modify_in_cs` (see `proto_hal::trace::Access`).
+    pub fn read(&self, via_modify_in_cs: bool) -> TokenStream {
+        match self.bus {
+            Some(..) => {
+                let complete = self.complete();
+                quote! { unsafe { read_raw(bus) }#complete }
+            }
+            None => quote! { unsafe { read_raw(#via_modify_in_cs) } },
+        }
+    }
+
+    /// A statement writing the raw register value.
+    ///
+    /// Traced accesses are flagged with `via_modify_in_cs` (see `proto_hal::trace::Access`).
+    pub fn write(&self, value: TokenStream, via_modify_in_cs: bool) -> TokenStream {
+        match self.bus {
+            Some(..) => {
+                let complete = self.complete();
+                quote! { unsafe { write_raw(bus, #value) }#complete; }
+            }
+            None => quote! { unsafe { write_raw(#value, #via_modify_in_cs) }; },
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod access;
+pub mod bus;
+pub mod lint;
 pub mod structures;
 pub mod utils;
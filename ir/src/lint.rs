@@ -0,0 +1,554 @@
+use std::collections::{HashMap, HashSet};
+
+use colored::Colorize;
+
+use crate::{
+    structures::{
+        entitlement::Entitlement,
+        field::{Field, Numericity},
+        hal::Hal,
+        interrupts::InterruptKind,
+        peripheral::Peripheral,
+        register::Register,
+        variant::Variant,
+    },
+    utils::diagnostic::{Context, Diagnostic, Diagnostics},
+};
+
+/// A check for model quality concerns which are not hard errors.
+///
+/// Lints are opt-in and only ever emit warnings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lint {
+    /// Peripherals, registers, fields, and variants without documentation.
+    MissingDocs,
+    /// Enumerated fields whose variants do not cover every bit pattern of the field.
+    VariantGaps,
+    /// Resolvable fields whose state is not read by any entitlement.
+    UnreferencedState,
+    /// Variants which can never be inhabited because their entitlements are unreachable from reset.
+    UnreachableVariant,
+    /// Registers with a reset value which sets bits not covered by any field.
+    UncoveredResetBits,
+    /// Interrupts which are not associated with any source peripheral.
+    UnassociatedInterrupt,
+    /// Runs of reserved interrupts which trail the table or are long enough to suggest
+    /// misnumbering.
+    ReservedRuns,
+}
+
+pub type Lints = HashSet<Lint>;
+
+impl Lint {
+    /// Every available lint.
+    pub fn all() -> Lints {
+        Lints::from([
+            Self::MissingDocs,
+            Self::VariantGaps,
+            Self::UnreferencedState,
+            Self::UnreachableVariant,
+            Self::UncoveredResetBits,
+            Self::UnassociatedInterrupt,
+            Self::ReservedRuns,
+        ])
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingDocs => "missing-docs",
+            Self::VariantGaps => "variant-gaps",
+            Self::UnreferencedState => "unreferenced-state",
+            Self::UnreachableVariant => "unreachable-variant",
+            Self::UncoveredResetBits => "uncovered-reset-bits",
+            Self::UnassociatedInterrupt => "unassociated-interrupt",
+            Self::ReservedRuns => "reserved-runs",
+        }
+    }
+
+    fn warning(&self, message: impl Into<String>) -> Diagnostic {
+        Diagnostic::warning(message).with_code(self.code())
+    }
+}
+
+/// A fully qualified path to a field state, i.e. `peripheral::register::field::Variant`.
+type StatePath = (String, String, String, String);
+
+fn state_path(entitlement: &Entitlement) -> StatePath {
+    (
+        entitlement.peripheral().to_string(),
+        entitlement.register().to_string(),
+        entitlement.field().to_string(),
+        entitlement.variant().to_string(),
+    )
+}
+
+impl Hal {
+    /// Check the HAL for model quality concerns.
+    ///
+    /// *Note: Lints assume the HAL is valid, so they are intended to be checked after validation.*
+    pub fn lint(&self, lints: &Lints) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        if lints.contains(&Lint::MissingDocs) {
+            diagnostics.extend(self.lint_missing_docs());
+        }
+
+        if lints.contains(&Lint::VariantGaps) {
+            diagnostics.extend(self.lint_variant_gaps());
+        }
+
+        if lints.contains(&Lint::UnreferencedState) {
+            diagnostics.extend(self.lint_unreferenced_states());
+        }
+
+        if lints.contains(&Lint::UnreachableVariant) {
+            diagnostics.extend(self.lint_unreachable_variants());
+        }
+
+        if lints.contains(&Lint::UncoveredResetBits) {
+            diagnostics.extend(self.lint_uncovered_reset_bits());
+        }
+
+        if lints.contains(&Lint::UnassociatedInterrupt) {
+            diagnostics.extend(self.lint_unassociated_interrupts());
+        }
+
+        if lints.contains(&Lint::ReservedRuns) {
+            diagnostics.extend(self.lint_reserved_runs());
+        }
+
+        diagnostics
+    }
+
+    /// Visit every field in the HAL along with the context it resides in.
+    fn fields(&self) -> impl Iterator<Item = (&Peripheral, &Register, &Field, Context)> {
+        self.peripherals.values().flat_map(|peripheral| {
+            peripheral.registers.values().flat_map(move |register| {
+                register.fields.values().map(move |field| {
+                    let context = Context::new()
+                        .and(peripheral.module_name().to_string())
+                        .and(register.module_name().to_string())
+                        .and(field.module_name().to_string());
+
+                    (peripheral, register, field, context)
+                })
+            })
+        })
+    }
+
+    fn lint_missing_docs(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        for peripheral in self.peripherals.values() {
+            let context = Context::new().and(peripheral.module_name().to_string());
+
+            if peripheral.docs.is_empty() {
+                diagnostics.insert(
+                    Lint::MissingDocs
+                        .warning("peripheral is not documented")
+                        .with_location(peripheral.location.clone())
+                        .with_context(context.clone()),
+                );
+            }
+
+            for register in peripheral.registers.values() {
+                if register.docs.is_empty() {
+                    diagnostics.insert(
+                        Lint::MissingDocs
+                            .warning("register is not documented")
+                            .with_location(register.location.clone())
+                            .with_context(context.clone().and(register.module_name().to_string())),
+                    );
+                }
+            }
+        }
+
+        for (.., field, context) in self.fields() {
+            if field.docs.is_empty() {
+                diagnostics.insert(
+                    Lint::MissingDocs
+                        .warning("field is not documented")
+                        .with_location(field.location.clone())
+                        .with_context(context.clone()),
+                );
+            }
+
+            for access in [field.access.get_read(), field.access.get_write()]
+                .into_iter()
+                .flatten()
+            {
+                let Numericity::Enumerated { variants } = &access.numericity else {
+                    continue;
+                };
+
+                for variant in variants.values().filter(|variant| variant.docs.is_empty()) {
+                    diagnostics.insert(
+                        Lint::MissingDocs
+                            .warning("variant is not documented")
+                            .with_location(variant.location.clone())
+                            .with_context(context.clone().and(variant.type_name().to_string())),
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    fn lint_variant_gaps(&self) -> Diagnostics {
+        // fields wider than this are assumed to intentionally be sparse
+        const MAX_WIDTH: u8 = 16;
+
+        let mut diagnostics = Diagnostics::new();
+
+        for (.., field, context) in self.fields() {
+            if field.width > MAX_WIDTH {
+                continue;
+            }
+
+            let read = field.access.get_read();
+            let write = field
+                .access
+                .get_write()
+                // symmetrical access properties need only be checked once
+                .filter(|write| !read.is_some_and(|read| std::ptr::eq(read, *write)));
+
+            for (access, direction) in [(read, "read"), (write, "write")] {
+                let Some(Numericity::Enumerated { variants }) =
+                    access.map(|access| &access.numericity)
+                else {
+                    continue;
+                };
+
+                let assigned = variants
+                    .values()
+                    .map(|variant| variant.bits)
+                    .collect::<HashSet<_>>();
+                let unassigned = (0..1u32 << field.width)
+                    .filter(|bits| !assigned.contains(bits))
+                    .collect::<Vec<_>>();
+
+                if unassigned.is_empty() {
+                    continue;
+                }
+
+                let mut notes = vec![format!(
+                    "unassigned: {}{}",
+                    unassigned
+                        .iter()
+                        .take(8)
+                        .map(|bits| format!("{bits:#x}"))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    if unassigned.len() > 8 { ", ..." } else { "" }
+                )];
+
+                if direction == "read" {
+                    notes.push(
+                        "reading an unassigned bit pattern from the hardware is undefined behavior"
+                            .to_string(),
+                    );
+                }
+
+                diagnostics.insert(
+                    Lint::VariantGaps
+                        .warning(format!(
+                            "{direction} variants of field [{}] leave {} bit patterns unassigned",
+                            field.module_name().to_string().bold(),
+                            unassigned.len()
+                        ))
+                        .notes(notes)
+                        .with_location(field.location.clone())
+                        .with_context(context.clone()),
+                );
+            }
+        }
+
+        diagnostics
+    }
+
+    fn lint_unreferenced_states(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        let mut referenced = HashSet::new();
+
+        for peripheral in self.peripherals.values() {
+            referenced.extend(peripheral.entitlements.iter().map(state_path));
+        }
+
+        for (.., field, _) in self.fields() {
+            referenced.extend(field.entitlements.iter().map(state_path));
+
+            for access in [field.access.get_read(), field.access.get_write()]
+                .into_iter()
+                .flatten()
+            {
+                referenced.extend(access.entitlements.iter().map(state_path));
+
+                if let Numericity::Enumerated { variants } = &access.numericity {
+                    for variant in variants.values() {
+                        referenced.extend(variant.entitlements.iter().map(state_path));
+                    }
+                }
+            }
+        }
+
+        let referenced_fields = referenced
+            .into_iter()
+            .map(|(peripheral, register, field, ..)| (peripheral, register, field))
+            .collect::<HashSet<_>>();
+
+        for (peripheral, register, field, context) in self.fields() {
+            if !field.is_resolvable() {
+                continue;
+            }
+
+            let path = (
+                peripheral.module_name().to_string(),
+                register.module_name().to_string(),
+                field.module_name().to_string(),
+            );
+
+            if !referenced_fields.contains(&path) {
+                diagnostics.insert(
+                    Lint::UnreferencedState
+                        .warning(format!(
+                            "state of resolvable field [{}] is not read by any entitlement",
+                            field.module_name().to_string().bold()
+                        ))
+                        .notes(["the state of this field is statically tracked but nothing depends on it"])
+                        .with_location(field.location.clone())
+                        .with_context(context),
+                );
+            }
+        }
+
+        diagnostics
+    }
+
+    fn lint_unreachable_variants(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        // every target must be reachable (as with peripheral and field entitlements)
+        let all_reachable = |entitlements: &HashSet<Entitlement>,
+                             reachable: &HashSet<StatePath>| {
+            entitlements
+                .iter()
+                .all(|entitlement| reachable.contains(&state_path(entitlement)))
+        };
+
+        // for each entitled field, at least one target must be reachable (as with variant entitlements)
+        let any_reachable_per_field =
+            |entitlements: &HashSet<Entitlement>, reachable: &HashSet<StatePath>| {
+                let mut fields = HashMap::<_, bool>::new();
+
+                for entitlement in entitlements {
+                    let path = state_path(entitlement);
+                    let satisfied = reachable.contains(&path);
+                    let (peripheral, register, field, ..) = path;
+
+                    *fields.entry((peripheral, register, field)).or_default() |= satisfied;
+                }
+
+                fields.values().all(|satisfied| *satisfied)
+            };
+
+        let states = self
+            .fields()
+            .filter_map(|(peripheral, register, field, context)| {
+                let Numericity::Enumerated { variants } = &field.resolvable()?.numericity else {
+                    None?
+                };
+
+                Some((peripheral, register, field, variants, context))
+            })
+            .collect::<Vec<_>>();
+
+        let path =
+            |peripheral: &Peripheral, register: &Register, field: &Field, variant: &Variant| {
+                (
+                    peripheral.module_name().to_string(),
+                    register.module_name().to_string(),
+                    field.module_name().to_string(),
+                    variant.type_name().to_string(),
+                )
+            };
+
+        // propagate reachability from the reset states until a fixed point is reached
+        let mut reachable = HashSet::new();
+
+        loop {
+            let mut changed = false;
+
+            for (peripheral, register, field, variants, ..) in &states {
+                if !all_reachable(&peripheral.entitlements, &reachable)
+                    || !all_reachable(&field.entitlements, &reachable)
+                {
+                    continue;
+                }
+
+                let reset = register.reset.map(|reset| field.reset_bits(reset));
+
+                for variant in variants.values() {
+                    if (reset == Some(variant.bits)
+                        || any_reachable_per_field(&variant.entitlements, &reachable))
+                        && reachable.insert(path(peripheral, register, field, variant))
+                    {
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        for (peripheral, register, field, variants, context) in states {
+            for variant in variants.values() {
+                if reachable.contains(&path(peripheral, register, field, variant)) {
+                    continue;
+                }
+
+                diagnostics.insert(
+                    Lint::UnreachableVariant
+                        .warning(format!(
+                            "variant [{}] is unreachable from reset",
+                            variant.type_name().to_string().bold()
+                        ))
+                        .notes([format!(
+                            "entitlements: {}",
+                            variant
+                                .entitlements
+                                .iter()
+                                .chain(&field.entitlements)
+                                .chain(&peripheral.entitlements)
+                                .map(ToString::to_string)
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )])
+                        .with_location(variant.location.clone())
+                        .with_context(context.clone().and(variant.type_name().to_string())),
+                );
+            }
+        }
+
+        diagnostics
+    }
+
+    fn lint_uncovered_reset_bits(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        for peripheral in self.peripherals.values() {
+            for register in peripheral.registers.values() {
+                let Some(reset) = register.reset else {
+                    continue;
+                };
+
+                let covered = register.fields.values().fold(0, |covered, field| {
+                    covered | field.reset_bits(u32::MAX) << field.offset
+                });
+
+                let uncovered = reset & !covered;
+
+                if uncovered != 0 {
+                    diagnostics.insert(
+                        Lint::UncoveredResetBits
+                            .warning(format!(
+                                "reset value sets bits {uncovered:#010x} which are not covered by any field"
+                            ))
+                            .with_location(register.location.clone())
+                            .with_context(
+                                Context::new()
+                                    .and(peripheral.module_name().to_string())
+                                    .and(register.module_name().to_string()),
+                            ),
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    fn lint_unassociated_interrupts(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        for interrupt in self.interrupts.iter() {
+            let InterruptKind::Handler(ident) = &interrupt.kind else {
+                continue;
+            };
+
+            if interrupt.sources.is_empty() {
+                diagnostics.insert(
+                    Lint::UnassociatedInterrupt
+                        .warning(format!(
+                            "interrupt [{}] is not associated with any peripheral",
+                            ident.to_string().bold()
+                        ))
+                        .notes(["associate the interrupt with the peripherals which raise it with `.sources(...)`"])
+                        .with_location(interrupt.location.clone())
+                        .with_context(Context::with_path(vec![
+                            "interrupts".to_string(),
+                            ident.to_string(),
+                        ])),
+                );
+            }
+        }
+
+        diagnostics
+    }
+
+    fn lint_reserved_runs(&self) -> Diagnostics {
+        /// The length of interior runs which is unlikely to be intentional.
+        const SUSPICIOUS_RUN: usize = 16;
+
+        let mut diagnostics = Diagnostics::new();
+        let context = Context::with_path(vec!["interrupts".to_string()]);
+
+        let mut runs = Vec::new();
+        let mut start = None;
+
+        for (position, interrupt) in self.interrupts.iter().enumerate() {
+            match (&interrupt.kind, start) {
+                (InterruptKind::Reserved, None) => start = Some(position),
+                (InterruptKind::Handler(..), Some(first)) => {
+                    runs.push((first, position));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(first) = start {
+            diagnostics.insert(
+                Lint::ReservedRuns
+                    .warning(format!(
+                        "the interrupt table ends with {} reserved interrupts (from position {first})",
+                        self.interrupts.len() - first
+                    ))
+                    .notes(["trailing reserved interrupts only lengthen the vector table"])
+                    .with_location(self.interrupts[first].location.clone())
+                    .with_context(context.clone()),
+            );
+        }
+
+        for (first, end) in runs {
+            if end - first >= SUSPICIOUS_RUN {
+                diagnostics.insert(
+                    Lint::ReservedRuns
+                        .warning(format!(
+                            "{} consecutive reserved interrupts at positions {first} through {}",
+                            end - first,
+                            end - 1
+                        ))
+                        .notes(["the positions of the following interrupts may be misnumbered"])
+                        .with_location(self.interrupts[first].location.clone())
+                        .with_context(context.clone()),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -1,14 +1,44 @@
-use std::ops::{Deref, DerefMut};
+use std::ops::Deref;
+
+use crate::utils::diagnostic::Diagnostics;
+
+pub mod cpu;
 pub mod entitlement;
 pub mod field;
 pub mod hal;
 pub mod interrupts;
+pub mod memory;
 pub mod peripheral;
 pub mod register;
 pub mod variant;
 
+/// A structure which has passed validation.
+///
+/// The structure is immutable once validated, such that the invariants
+/// established by validation cannot be violated.
+#[derive(Debug)]
 pub struct Validated<S> {
     structure: S,
+    warnings: Diagnostics,
+}
+
+impl<S> Validated<S> {
+    pub(crate) fn new(structure: S, warnings: Diagnostics) -> Self {
+        Self {
+            structure,
+            warnings,
+        }
+    }
+
+    /// The non-fatal diagnostics emitted during validation.
+    pub fn warnings(&self) -> &Diagnostics {
+        &self.warnings
+    }
+
+    /// Release the structure, discarding the validation guarantees.
+    pub fn into_inner(self) -> S {
+        self.structure
+    }
 }
 
 impl<S> Deref for Validated<S> {
@@ -19,12 +49,6 @@ impl<S> Deref for Validated<S> {
     }
 }
 
-impl<S> DerefMut for Validated<S> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.structure
-    }
-}
-
 pub trait Ident {
     fn ident(&self) -> &str;
 }
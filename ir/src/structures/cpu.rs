@@ -0,0 +1,142 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+
+use crate::utils::diagnostic::{Context, Diagnostic, Diagnostics, Location};
+
+/// The Cortex-M core flavors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Core {
+    M0,
+    M0Plus,
+    M3,
+    M4,
+    M7,
+    M33,
+}
+
+/// The architecture profiles implemented by the core flavors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Architecture {
+    V6M,
+    V7M,
+    V8M,
+}
+
+impl Core {
+    pub fn architecture(&self) -> Architecture {
+        match self {
+            Self::M0 | Self::M0Plus => Architecture::V6M,
+            Self::M3 | Self::M4 | Self::M7 => Architecture::V7M,
+            Self::M33 => Architecture::V8M,
+        }
+    }
+
+    /// Whether the core can be implemented with a floating point unit.
+    pub fn supports_fpu(&self) -> bool {
+        matches!(self, Self::M4 | Self::M7 | Self::M33)
+    }
+
+    /// Whether the core can be implemented with a memory protection unit.
+    pub fn supports_mpu(&self) -> bool {
+        !matches!(self, Self::M0)
+    }
+}
+
+impl Architecture {
+    /// The maximum number of external interrupts the NVIC supports.
+    pub fn max_interrupts(&self) -> usize {
+        match self {
+            Self::V6M => 32,
+            Self::V7M => 240,
+            Self::V8M => 480,
+        }
+    }
+}
+
+/// Metadata of the processor core which the HAL targets.
+#[derive(Debug, Clone)]
+pub struct Cpu {
+    pub core: Core,
+    /// The number of priority bits implemented by the NVIC.
+    pub priority_bits: u8,
+    /// Whether the core implements a floating point unit.
+    pub fpu: bool,
+    /// Whether the core implements a memory protection unit.
+    pub mpu: bool,
+    pub location: Location,
+}
+
+impl Cpu {
+    #[track_caller]
+    pub fn new(core: Core, priority_bits: u8) -> Self {
+        Self {
+            core,
+            priority_bits,
+            fpu: false,
+            mpu: false,
+            location: Location::caller(),
+        }
+    }
+
+    /// Declare the core implements a floating point unit.
+    pub fn fpu(mut self) -> Self {
+        self.fpu = true;
+        self
+    }
+
+    /// Declare the core implements a memory protection unit.
+    pub fn mpu(mut self) -> Self {
+        self.mpu = true;
+        self
+    }
+
+    pub fn validate(&self, context: &Context) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        if !(1..=8).contains(&self.priority_bits) {
+            diagnostics.insert(
+                Diagnostic::error(format!(
+                    "NVIC priority bits must be within 1 and 8, found {}.",
+                    self.priority_bits
+                ))
+                .with_code("invalid-priority-bits")
+                .with_location(self.location.clone())
+                .with_context(context.clone()),
+            );
+        }
+
+        for (present, supported, unit) in [
+            (self.fpu, self.core.supports_fpu(), "floating point unit"),
+            (self.mpu, self.core.supports_mpu(), "memory protection unit"),
+        ] {
+            if present && !supported {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "the {:?} core cannot implement a {unit}.",
+                        self.core
+                    ))
+                    .with_code("cpu-unit-unsupported")
+                    .with_location(self.location.clone())
+                    .with_context(context.clone()),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
+
+impl ToTokens for Cpu {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let priority_bits = self.priority_bits;
+        let fpu = self.fpu;
+
+        tokens.extend(quote! {
+            /// The number of priority bits implemented by the NVIC.
+            pub const NVIC_PRIO_BITS: u8 = #priority_bits;
+
+            /// Whether the core implements a floating point unit.
+            pub const FPU_PRESENT: bool = #fpu;
+        });
+    }
+}
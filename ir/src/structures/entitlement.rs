@@ -1,11 +1,17 @@
-use std::{collections::HashSet, fmt::Display};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    hash::{Hash, Hasher},
+};
 
 use proc_macro2::Span;
 use syn::{Ident, Path, parse_quote};
 use ters::ters;
 
+use crate::utils::diagnostic::Location;
+
 #[ters]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct Entitlement {
     #[get]
     peripheral: Ident,
@@ -15,9 +21,33 @@ pub struct Entitlement {
     field: Ident,
     #[get]
     variant: Ident,
+    #[get]
+    location: Location,
+}
+
+// note: the definition location does not participate in equality
+impl PartialEq for Entitlement {
+    fn eq(&self, other: &Self) -> bool {
+        self.peripheral == other.peripheral
+            && self.register == other.register
+            && self.field == other.field
+            && self.variant == other.variant
+    }
+}
+
+impl Eq for Entitlement {}
+
+impl Hash for Entitlement {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.peripheral.hash(state);
+        self.register.hash(state);
+        self.field.hash(state);
+        self.variant.hash(state);
+    }
 }
 
 impl Entitlement {
+    #[track_caller]
     pub fn to(path: impl AsRef<str>) -> Self {
         let mut path = path.as_ref().split("::");
 
@@ -26,6 +56,7 @@ impl Entitlement {
             register: Ident::new(path.next().unwrap_or("unknown"), Span::call_site()),
             field: Ident::new(path.next().unwrap_or("unknown"), Span::call_site()),
             variant: Ident::new(path.next().unwrap_or("unknown"), Span::call_site()),
+            location: Location::caller(),
         }
     }
 
@@ -7,8 +7,12 @@ use syn::{Ident, Path, Type, parse_quote};
 
 use crate::{
     access::{Access, AccessProperties, HardwareAccess, ReadWrite},
+    bus::Interface,
     structures::entitlement::{Entitlement, Entitlements},
-    utils::diagnostic::{Context, Diagnostic, Diagnostics},
+    utils::{
+        diagnostic::{Context, Diagnostic, Diagnostics, Location},
+        naming::{self, Namespace},
+    },
 };
 
 use super::variant::Variant;
@@ -39,10 +43,13 @@ pub struct Field {
     pub access: Access,
     pub entitlements: Entitlements,
     pub hardware_access: Option<HardwareAccess>,
+    pub wake_from: Option<Ident>,
     pub docs: Vec<String>,
+    pub location: Location,
 }
 
 impl Field {
+    #[track_caller]
     pub fn new(ident: impl AsRef<str>, offset: u8, width: u8, access: Access) -> Self {
         Self {
             ident: Ident::new(ident.as_ref(), Span::call_site()),
@@ -51,7 +58,9 @@ impl Field {
             access,
             entitlements: Entitlements::new(),
             hardware_access: None,
+            wake_from: None,
             docs: Vec::new(),
+            location: Location::caller(),
         }
     }
 
@@ -67,6 +76,15 @@ impl Field {
         }
     }
 
+    /// Designate the interrupt which signals changes of this (hardware-written) field, so tasks can
+    /// asynchronously wait for variants of the field.
+    pub fn wake_from(self, interrupt: impl AsRef<str>) -> Self {
+        Self {
+            wake_from: Some(Ident::new(interrupt.as_ref(), Span::call_site())),
+            ..self
+        }
+    }
+
     pub fn docs<I>(mut self, docs: I) -> Self
     where
         I: IntoIterator,
@@ -80,18 +98,78 @@ impl Field {
 
     pub fn module_name(&self) -> Ident {
         Ident::new(
-            self.ident.to_string().to_lowercase().as_str(),
+            naming::lower_case(self.ident.to_string().as_str()).as_str(),
             Span::call_site(),
         )
     }
 
     pub fn type_name(&self) -> Ident {
         Ident::new(
-            inflector::cases::pascalcase::to_pascal_case(self.ident.to_string().as_str()).as_str(),
+            naming::pascal_case(self.ident.to_string().as_str()).as_str(),
             Span::call_site(),
         )
     }
 
+    /// Whether the hardware writes to this field (specified or inferred).
+    pub fn is_hardware_written(&self) -> bool {
+        let inferred = if self.access.is_read() && !self.access.is_write() {
+            HardwareAccess::Write
+        } else {
+            HardwareAccess::ReadOnly
+        };
+
+        self.hardware_access.unwrap_or(inferred) == HardwareAccess::Write
+    }
+
+    /// The variants software can wait for the hardware to write.
+    ///
+    /// *Note: Fields with read entitlements are not waitable.*
+    pub(crate) fn waitable_variants(&self) -> Option<&HashMap<Ident, Variant>> {
+        if !self.is_hardware_written() {
+            None?
+        }
+
+        let read = self.access.get_read()?;
+
+        if !read.entitlements.is_empty() {
+            None?
+        }
+
+        match &read.numericity {
+            Numericity::Enumerated { variants } => Some(variants),
+            Numericity::Numeric => None,
+        }
+    }
+
+    /// The variants adoption resolves the field into.
+    ///
+    /// *Note: Fields with read entitlements are not resolved.*
+    pub(crate) fn resolved_variants(&self) -> Option<&HashMap<Ident, Variant>> {
+        let access = self.resolvable()?;
+
+        if !access.entitlements.is_empty() {
+            None?
+        }
+
+        match &access.numericity {
+            Numericity::Enumerated { variants } => Some(variants),
+            Numericity::Numeric => None,
+        }
+    }
+
+    /// The type of the field state reconstructed by adoption.
+    pub(crate) fn adopted_ty(&self) -> TokenStream {
+        let ident = self.module_name();
+
+        if !self.entitlements.is_empty() {
+            quote! { #ident::Unavailable }
+        } else if self.resolved_variants().is_some() {
+            quote! { Result<#ident::Resolved, #ident::Dynamic> }
+        } else {
+            quote! { #ident::Dynamic }
+        }
+    }
+
     pub fn is_resolvable(&self) -> bool {
         self.resolvable().is_some()
     }
@@ -129,6 +207,15 @@ impl Field {
         }
     }
 
+    /// Extract the bits of this field from the reset value of the register it resides in.
+    pub(crate) fn reset_bits(&self, register_reset: u32) -> u32 {
+        let mask = u32::MAX
+            .checked_shr(32u32.saturating_sub(self.width.into()))
+            .unwrap_or(0);
+
+        register_reset.checked_shr(self.offset.into()).unwrap_or(0) & mask
+    }
+
     pub(crate) fn reset_ty(&self, register_reset: Option<u32>) -> Type {
         if !self.entitlements.is_empty() {
             return parse_quote! { Unavailable };
@@ -142,20 +229,23 @@ impl Field {
             return parse_quote! { Dynamic };
         }
 
-        let register_reset =
-            register_reset.expect("fields which are all of: [readable, resolvable, unentitled] must have a reset value specified");
+        // note: validation guarantees fields which are all of: [readable, resolvable, unentitled]
+        // have a reset value which corresponds to a state, but should that not be the case,
+        // `Dynamic` is always a sound fallback
+        let Some(register_reset) = register_reset else {
+            return parse_quote! { Dynamic };
+        };
 
-        let mask = u32::MAX >> (32 - self.width);
-        let reset = (register_reset >> self.offset) & mask;
+        let reset = self.reset_bits(register_reset);
 
         match &read.numericity {
             Numericity::Numeric => parse_quote! { Value::<#reset> },
             Numericity::Enumerated { variants } => {
-                let ty = variants
-                    .values()
-                    .find(|variant| variant.bits == reset)
-                    .expect("exactly one variant must correspond to the reset value")
-                    .type_name();
+                let Some(variant) = variants.values().find(|variant| variant.bits == reset) else {
+                    return parse_quote! { Dynamic };
+                };
+
+                let ty = variant.type_name();
 
                 parse_quote! { #ty }
             }
@@ -166,6 +256,32 @@ impl Field {
         let new_context = context.clone().and(self.ident.clone().to_string());
         let mut diagnostics = Diagnostics::new();
 
+        if self.width == 0 {
+            diagnostics.insert(
+                Diagnostic::error("field width must be nonzero")
+                    .with_code("field-zero-width")
+                    .with_location(self.location.clone())
+                    .with_context(new_context.clone()),
+            );
+        }
+
+        if let Some(interrupt) = &self.wake_from
+            && self.waitable_variants().is_none()
+        {
+            diagnostics.insert(
+                Diagnostic::error(format!(
+                    "field is woken from [{}] but cannot be waited for",
+                    interrupt.to_string().bold()
+                ))
+                .notes([
+                    "only enumerated fields which are written by hardware and readable without entitlements can be waited for",
+                ])
+                .with_code("wake-unwaitable")
+                .with_location(self.location.clone())
+                .with_context(new_context.clone()),
+            );
+        }
+
         let validate_numericity = |numericity: &Numericity, diagnostics: &mut Diagnostics| {
             match numericity {
                 Numericity::Numeric => (),
@@ -173,19 +289,21 @@ impl Field {
                     if let Some(largest_variant) =
                         variants.values().map(|variant| variant.bits).max()
                     {
-                        let variant_limit = (1 << self.width) - 1; // note: this will break if a 32 bit enumerated field were described but that is not likely
-                        if largest_variant > variant_limit {
+                        let variant_limit = (1u64 << self.width.min(32)) - 1;
+                        if u64::from(largest_variant) > variant_limit {
                             diagnostics.insert(
                                 Diagnostic::error(format!(
                             "field variants exceed field width. (largest variant: {largest_variant}, largest possible: {variant_limit})",
                         ))
+                                .with_code("variant-exceeds-width")
+                                .with_location(self.location.clone())
                                 .with_context(new_context.clone()),
                             );
                         }
                     }
 
                     let mut sorted_variants = variants.values().collect::<Vec<_>>();
-                    sorted_variants.sort_by(|lhs, rhs| lhs.bits.cmp(&rhs.bits));
+                    sorted_variants.sort_by_key(|variant| variant.bits);
 
                     // validate variant adjacency
                     for window in sorted_variants.windows(2) {
@@ -199,6 +317,9 @@ impl Field {
                                     lhs.ident.to_string().bold(),
                                     rhs.ident.to_string().bold()
                                 ))
+                                .with_code("variant-overlap")
+                                .with_location(rhs.location.clone())
+                                .notes([format!("[{}] is defined at {}", lhs.ident, lhs.location)])
                                 .with_context(new_context.clone()),
                             );
                         }
@@ -226,6 +347,8 @@ impl Field {
                                             .to_string(),
                                     )
                                     .notes([format!("erroneous entitlement: \"{entitlement}\"")])
+                                    .with_code("entanglement-across-registers")
+                                    .with_location(entitlement.location().clone())
                                     .with_context(
                                         new_context.clone().and(variant.ident.to_string()),
                                     ),
@@ -244,12 +367,60 @@ impl Field {
             validate_numericity(&access.numericity, &mut diagnostics);
 
             if let Numericity::Enumerated { variants } = &access.numericity {
+                // variant accessors and `is_<variant>` methods
+                let mut namespace = Namespace::new("variant method set");
+
+                for variant in variants.values() {
+                    namespace.insert(
+                        "variant",
+                        &variant.ident,
+                        &variant.location,
+                        naming::snake_case,
+                    );
+                    namespace.insert(
+                        "variant",
+                        &variant.ident,
+                        &variant.location,
+                        naming::pascal_case,
+                    );
+                }
+
+                diagnostics.extend(namespace.validate(&new_context));
+
                 for variant in variants.values() {
                     diagnostics.extend(variant.validate(&new_context));
                 }
             }
         }
 
+        // states of resolvable fields reside in the field module
+        if let Some(resolvable) = self.resolvable()
+            && let Numericity::Enumerated { variants } = &resolvable.numericity
+        {
+            let mut namespace = Namespace::new("field module").reserve([
+                "Dynamic",
+                "Value",
+                "Variant",
+                "ReadVariant",
+                "WriteVariant",
+                "Numeric",
+                "Field",
+                "Unavailable",
+                "Resolved",
+            ]);
+
+            for variant in variants.values() {
+                namespace.insert(
+                    "variant",
+                    &variant.ident,
+                    &variant.location,
+                    naming::pascal_case,
+                );
+            }
+
+            diagnostics.extend(namespace.validate(&new_context));
+        }
+
         // validate access entitlements
         if let (Some(read), Some(..)) = (self.access.get_read(), self.access.get_write())
             && !read.entitlements.is_empty()
@@ -257,6 +428,8 @@ impl Field {
             diagnostics.insert(
                 Diagnostic::error("writable fields cannot be conditionally readable")
                     .notes(["for more information, refer to the \"Access Entitlement Quandaries\" section in `notes.md`"])
+                    .with_code("conditionally-readable-writable")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
         }
@@ -271,6 +444,8 @@ impl Field {
                     .notes([
                         "for more information, refer to the \"Inertness\" section in `notes.md`",
                     ])
+                    .with_code("readable-inert")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
         }
@@ -287,6 +462,8 @@ impl Field {
             diagnostics.insert(
                 Diagnostic::error("field value retainment is ambiguous")
                     .notes(["specify the hardware field access with `.hardware_access(...)` to disambiguate how this field retains values"])
+                    .with_code("ambiguous-retainment")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
         }
@@ -307,6 +484,8 @@ impl Field {
             {
                 diagnostics.insert(
                 Diagnostic::warning(format!("hardware access specified as {hardware_access:?} when it can be inferred as such"))
+                    .with_code("redundant-hardware-access")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
             }
@@ -318,6 +497,8 @@ impl Field {
             diagnostics.insert(
                 Diagnostic::error(format!("\"{}\" is a reserved keyword", self.module_name()))
                     .notes([format!("reserved field keywords are: {reserved:?}")])
+                    .with_code("reserved-field-ident")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
         }
@@ -364,7 +545,7 @@ impl Field {
         quote! {
             pub struct Dynamic {
                 #(
-                    #[expect(unused)] #entitlement_idents: ::proto_hal::stasis::Entitlement<#entitlement_paths>,
+                    #entitlement_idents: ::proto_hal::stasis::Entitlement<#entitlement_paths>,
                 )*
 
                 _sealed: (),
@@ -475,6 +656,16 @@ impl Field {
                         }
                     }
 
+                    /// The variant corresponding to the source bits, if any.
+                    pub fn try_from_bits(bits: u32) -> Option<Self> {
+                        match bits {
+                            #(
+                                #variant_bits => Some(Self::#variant_idents),
+                            )*
+                            _ => None,
+                        }
+                    }
+
                     #(
                         pub fn #is_variant_idents(&self) -> bool {
                             matches!(self, Self::#variant_idents)
@@ -699,14 +890,26 @@ impl Field {
     }
 
     fn generate_unavailable(
+        resolvable: bool,
         entitlement_idents: &Vec<Ident>,
         entitlement_paths: &Vec<Path>,
     ) -> TokenStream {
+        // fields which are not resolvable have no incoming states, so the unavailable state can only
+        // be consumed (such as when the peripheral is reverted)
+        let outgoing = (!resolvable).then(|| {
+            quote! {
+                impl ::proto_hal::stasis::Position<Field> for Unavailable {}
+                impl ::proto_hal::stasis::Outgoing<Field> for Unavailable {}
+            }
+        });
+
         quote! {
             pub struct Unavailable {
                 _sealed: (),
             }
 
+            #outgoing
+
             impl ::proto_hal::stasis::Conjure for Unavailable {
                 unsafe fn conjure() -> Self {
                     Self {
@@ -723,12 +926,206 @@ impl Field {
                     }
                 }
             }
+
+            impl Dynamic {
+                /// Return the field to the unavailable state, releasing the entitlements it was unmasked with.
+                pub fn mask(self) -> (Unavailable, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*) {
+                    (
+                        Unavailable {
+                            _sealed: (),
+                        },
+                        #(
+                            self.#entitlement_idents,
+                        )*
+                    )
+                }
+            }
         }
     }
 }
 
-impl ToTokens for Field {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+impl Field {
+    /// Functions waiting for the hardware to write variants of the field.
+    ///
+    /// *Note: Bus-backed accesses are fallible and require the bus, so no wait functions are generated.*
+    fn generate_wait(&self, interface: Interface) -> Option<TokenStream> {
+        if interface.bus().is_some() {
+            None?
+        }
+
+        let variants = self.waitable_variants()?;
+
+        let field_ident = self.module_name();
+        let variant_idents = variants
+            .values()
+            .map(|variant| variant.type_name())
+            .collect::<Vec<_>>();
+        let is_variant_idents = variants
+            .values()
+            .map(|variant| format_ident!("is_{}", variant.module_name()))
+            .collect::<Vec<_>>();
+        let wait_idents = variants
+            .values()
+            .map(|variant| format_ident!("wait_until_{}", variant.module_name()))
+            .collect::<Vec<_>>();
+
+        let mut out = quote! {
+            #(
+                #[doc = concat!("Block until the hardware writes `", stringify!(#variant_idents), "` to the field.")]
+                ///
+                /// If `limit` is specified, at most `limit + 1` reads are performed before giving up.
+                pub fn #wait_idents(#[expect(unused)] instance: &mut Dynamic, limit: Option<u32>) -> Result<(), ::proto_hal::wait::Timeout> {
+                    let mut remaining = limit;
+
+                    loop {
+                        if unsafe { super::read_untracked() }.#field_ident().#is_variant_idents() {
+                            return Ok(());
+                        }
+
+                        if let Some(remaining) = &mut remaining {
+                            if *remaining == 0 {
+                                return Err(::proto_hal::wait::Timeout);
+                            }
+
+                            *remaining -= 1;
+                        }
+
+                        ::core::hint::spin_loop();
+                    }
+                }
+            )*
+        };
+
+        if let Some(interrupt) = &self.wake_from {
+            let async_wait_idents = wait_idents
+                .iter()
+                .map(|ident| format_ident!("{ident}_async"));
+
+            out.extend(quote! {
+                #[doc(hidden)]
+                pub static WAKER: ::proto_hal::wait::Waker = ::proto_hal::wait::Waker::new();
+
+                #(
+                    #[doc = concat!("Wait until the hardware writes `", stringify!(#variant_idents), "` to the field.")]
+                    ///
+                    #[doc = concat!("The task is woken when `wake::", stringify!(#interrupt), "` is called from the interrupt handler.")]
+                    pub async fn #async_wait_idents(#[expect(unused)] instance: &mut Dynamic) {
+                        ::core::future::poll_fn(|cx| {
+                            // register first so a write between the read and registration is not missed
+                            WAKER.register(cx.waker());
+
+                            if unsafe { super::read_untracked() }.#field_ident().#is_variant_idents() {
+                                ::core::task::Poll::Ready(())
+                            } else {
+                                ::core::task::Poll::Pending
+                            }
+                        })
+                        .await
+                    }
+                )*
+            });
+        }
+
+        Some(out)
+    }
+
+    /// Functions recovering the static state of the field from the hardware.
+    ///
+    /// *Note: Fields with read entitlements and bus-backed fields cannot be resolved at runtime.*
+    fn generate_resolve(&self, interface: Interface) -> Option<TokenStream> {
+        if interface.bus().is_some() {
+            None?
+        }
+
+        let access = self.resolvable()?;
+
+        if !access.entitlements.is_empty() {
+            None?
+        }
+
+        let field_ident = self.module_name();
+
+        let (raw_ty, read) = match &access.numericity {
+            Numericity::Enumerated { .. } => (quote! { ReadVariant }, quote! { Self::bits() }),
+            Numericity::Numeric => (
+                quote! { u32 },
+                quote! { unsafe { super::read_untracked() }.#field_ident() },
+            ),
+        };
+        let raw = match &access.numericity {
+            Numericity::Enumerated { .. } => quote! { S::RAW as u32 },
+            Numericity::Numeric => quote! { S::RAW },
+        };
+
+        let mut out = quote! {
+            impl Dynamic {
+                /// Read the field and resolve it into the state `S` if the field holds it.
+                ///
+                /// Otherwise, the dynamic state is returned.
+                pub fn try_resolve<S>(self) -> Result<S, Self>
+                where
+                    S: ::proto_hal::stasis::Incoming<Field, Raw = #raw_ty>,
+                {
+                    if #read == #raw {
+                        Ok(unsafe { <S as ::proto_hal::stasis::Conjure>::conjure() })
+                    } else {
+                        Err(self)
+                    }
+                }
+            }
+        };
+
+        if let Numericity::Enumerated { variants } = &access.numericity {
+            let variant_idents = variants
+                .values()
+                .map(|variant| variant.type_name())
+                .collect::<Vec<_>>();
+
+            out.extend(quote! {
+                /// The static states the field can be resolved into.
+                pub enum Resolved {
+                    #(
+                        #variant_idents(#variant_idents),
+                    )*
+                }
+
+                impl Resolved {
+                    /// # Safety
+                    /// The field must hold the variant, and the state must not exist elsewhere.
+                    pub unsafe fn conjure(variant: ReadVariant) -> Self {
+                        match variant {
+                            #(
+                                ReadVariant::#variant_idents => Self::#variant_idents(unsafe { <#variant_idents as ::proto_hal::stasis::Conjure>::conjure() }),
+                            )*
+                        }
+                    }
+                }
+
+                impl Dynamic {
+                    /// The raw bits the field holds, which need not correspond to a variant.
+                    fn bits() -> u32 {
+                        (unsafe { super::read_untracked() }.bits() >> OFFSET) & (u32::MAX >> (32 - WIDTH))
+                    }
+
+                    /// Read the field and resolve it into the state it holds.
+                    ///
+                    /// Otherwise (if the field holds a value which does not correspond to any
+                    /// variant), the dynamic state is returned.
+                    pub fn resolve(self) -> Result<Resolved, Self> {
+                        match ReadVariant::try_from_bits(Self::bits()) {
+                            Some(variant) => Ok(unsafe { Resolved::conjure(variant) }),
+                            None => Err(self),
+                        }
+                    }
+                }
+            });
+        }
+
+        Some(out)
+    }
+
+    /// Generate the field module with the specified register access interface.
+    pub(crate) fn generate(&self, interface: Interface) -> TokenStream {
         let ident = &self.ident;
 
         let mut body = quote! {};
@@ -742,6 +1139,8 @@ impl ToTokens for Field {
         body.extend(Self::generate_repr(&self.ident, &self.access));
         body.extend(Self::generate_trait_impls(self));
         body.extend(Self::generate_marker_ty(&self.entitlements));
+        body.extend(self.generate_wait(interface));
+        body.extend(self.generate_resolve(interface));
 
         let mut entitlements = self.entitlements.iter().collect::<Vec<_>>();
         entitlements.sort_by(|lhs, rhs| lhs.field().cmp(rhs.field()));
@@ -763,6 +1162,7 @@ impl ToTokens for Field {
 
         if !self.entitlements.is_empty() {
             body.extend(Self::generate_unavailable(
+                self.is_resolvable(),
                 &entitlement_idents,
                 &entitlement_paths,
             ));
@@ -771,13 +1171,19 @@ impl ToTokens for Field {
         let docs = &self.docs;
 
         // final module
-        tokens.extend(quote! {
+        quote! {
             #(
                 #[doc = #docs]
             )*
             pub mod #ident {
                 #body
             }
-        });
+        }
+    }
+}
+
+impl ToTokens for Field {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.generate(Interface::new(None)));
     }
 }
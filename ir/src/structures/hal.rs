@@ -1,21 +1,38 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use colored::Colorize;
-use proc_macro2::TokenStream;
-use quote::{ToTokens, quote};
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
 use syn::Ident;
 
 use crate::{
-    structures::interrupts::{Interrupt, Interrupts},
-    utils::diagnostic::{Context, Diagnostic, Diagnostics},
+    bus::Bus,
+    structures::{
+        Validated,
+        interrupts::{Interrupt, InterruptKind, Interrupts, Runtime},
+    },
+    utils::{
+        diagnostic::{Context, Diagnostic, Diagnostics, Kind},
+        naming::{self, Namespace},
+    },
 };
 
-use super::{entitlement::Entitlement, field::Numericity, peripheral::Peripheral};
+use super::{
+    cpu::Cpu,
+    entitlement::Entitlement,
+    field::Numericity,
+    memory::{self, Region},
+    peripheral::Peripheral,
+};
 
 #[derive(Debug, Clone)]
 pub struct Hal {
     pub peripherals: HashMap<Ident, Peripheral>,
     pub interrupts: Interrupts,
+    pub bus: Option<Bus>,
+    pub cpu: Option<Cpu>,
+    pub memory: Vec<Region>,
+    pub reset_on_startup: bool,
 }
 
 impl Hal {
@@ -27,65 +44,164 @@ impl Hal {
                     .map(|peripheral| (peripheral.ident.clone(), peripheral)),
             ),
             interrupts: Interrupts::empty(),
+            bus: None,
+            cpu: None,
+            memory: Vec::new(),
+            reset_on_startup: false,
         }
     }
 
+    /// Add peripherals to the HAL, replacing any existing peripherals with the same identifier.
+    pub fn peripherals(mut self, peripherals: impl IntoIterator<Item = Peripheral>) -> Self {
+        self.peripherals.extend(
+            peripherals
+                .into_iter()
+                .map(|peripheral| (peripheral.ident.clone(), peripheral)),
+        );
+        self
+    }
+
     pub fn interrupts(mut self, interrupts: impl IntoIterator<Item = Interrupt>) -> Self {
         self.interrupts.extend(interrupts);
         self
     }
 
-    pub fn render_raw(&self) -> String {
-        self.to_token_stream().to_string()
+    /// Select the runtime which the interrupt vector table is generated for.
+    pub fn runtime(mut self, runtime: Runtime) -> Self {
+        self.interrupts.runtime = runtime;
+        self
     }
 
-    pub fn render(&self) -> Result<String, String> {
-        let content = self.to_token_stream().to_string();
-        let parsed = syn::parse_file(content.as_str());
-
-        match parsed {
-            Ok(file) => Ok(prettyplease::unparse(&file)),
-            Err(e) => {
-                let start = e.span().start().column;
-                let end = e.span().end().column;
+    /// Declare the processor core, emitting the device contract expected by RTIC
+    /// (`Interrupt`, `NVIC_PRIO_BITS` and an `InterruptNumber` implementation).
+    pub fn cpu(mut self, cpu: Cpu) -> Self {
+        self.cpu = Some(cpu);
+        self
+    }
 
-                const PADDING: usize = 50;
+    /// Declare that the peripherals are in the reset state when the application starts (i.e. no
+    /// bootloader hands over modified peripherals), such that the generated `Peripherals::take`
+    /// is available without declaring so at runtime.
+    pub fn reset_on_startup(mut self) -> Self {
+        self.reset_on_startup = true;
+        self
+    }
 
-                let lhs = &content[start - PADDING..start];
-                let err = &content[start..end].red();
-                let rhs = &content[end..end + PADDING];
+    /// Describe the regions of the device memory map.
+    pub fn memory(mut self, regions: impl IntoIterator<Item = Region>) -> Self {
+        self.memory.extend(regions);
+        self
+    }
 
-                Err(format!("{}:\n{lhs}{err}{rhs}", e))
-            }
-        }
+    /// Model an external device whose registers are accessed through a bus rather than memory-mapped.
+    pub fn bus(mut self, bus: Bus) -> Self {
+        self.bus = Some(bus);
+        self
     }
 }
 
 impl Hal {
-    pub fn validate(&self) -> Diagnostics {
+    /// Validate the HAL model.
+    ///
+    /// Codegen is only available for validated HALs, so every invariant codegen
+    /// relies upon must be checked here.
+    ///
+    /// If any errors are emitted, all diagnostics are returned. Otherwise, the
+    /// validated HAL is returned carrying any warnings.
+    pub fn validate(self) -> Result<Validated<Self>, Diagnostics> {
+        let diagnostics = self.diagnostics();
+
+        if diagnostics
+            .iter()
+            .any(|diagnostic| matches!(diagnostic.kind(), Kind::Error))
+        {
+            Err(diagnostics)
+        } else {
+            Ok(Validated::new(self, diagnostics))
+        }
+    }
+
+    fn diagnostics(&self) -> Diagnostics {
         let mut diagnostics = Diagnostics::new();
         let new_context = Context::new();
 
-        let mut sorted_peripherals = self.peripherals.values().collect::<Vec<_>>();
-        sorted_peripherals.sort_by(|lhs, rhs| lhs.base_addr.cmp(&rhs.base_addr));
+        // device register addresses are not subject to memory-mapped layout rules
+        if self.bus.is_none() {
+            let mut sorted_peripherals = self.peripherals.values().collect::<Vec<_>>();
+            sorted_peripherals.sort_by_key(|peripheral| peripheral.base_addr);
 
-        for window in sorted_peripherals.windows(2) {
-            let lhs = window[0];
-            let rhs = window[1];
+            for window in sorted_peripherals.windows(2) {
+                let lhs = window[0];
+                let rhs = window[1];
 
-            if lhs.base_addr + lhs.width() > rhs.base_addr {
+                if u64::from(lhs.base_addr) + u64::from(lhs.width()) > u64::from(rhs.base_addr) {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "peripherals [{}] and [{}] overlap.",
+                            lhs.ident, rhs.ident
+                        ))
+                        .with_code("peripheral-overlap")
+                        .with_location(rhs.location.clone())
+                        .with_context(new_context.clone()),
+                    );
+                }
+            }
+        }
+
+        let mut namespace = Namespace::new("HAL crate root").reserve([
+            "Peripherals",
+            "AdoptedPeripherals",
+            "interrupt",
+            "Interrupt",
+            "memory",
+            "wake",
+        ]);
+
+        for peripheral in self.peripherals.values() {
+            namespace.insert(
+                "peripheral",
+                &peripheral.ident,
+                &peripheral.location,
+                str::to_string,
+            );
+        }
+
+        diagnostics.extend(namespace.validate(&new_context));
+
+        diagnostics.extend(self.memory_diagnostics());
+
+        if let Some(cpu) = &self.cpu {
+            diagnostics.extend(cpu.validate(&new_context));
+
+            let max = cpu.core.architecture().max_interrupts();
+
+            if self.interrupts.len() > max {
                 diagnostics.insert(
                     Diagnostic::error(format!(
-                        "peripherals [{}] and [{}] overlap.",
-                        lhs.ident, rhs.ident
+                        "the interrupt table has {} entries but the {:?} core supports at most {max} external interrupts.",
+                        self.interrupts.len(),
+                        cpu.core
                     ))
+                    .with_code("interrupt-table-overflow")
+                    .with_location(cpu.location.clone())
+                    .with_context(Context::with_path(vec!["interrupts".to_string()])),
+                );
+            }
+
+            if self.bus.is_some() || !self.interrupts.runtime.nvic() {
+                diagnostics.insert(
+                    Diagnostic::error(
+                        "core metadata requires a memory-mapped HAL using the cortex-m-rt runtime.",
+                    )
+                    .with_code("cpu-unsupported")
+                    .with_location(cpu.location.clone())
                     .with_context(new_context.clone()),
                 );
             }
         }
 
         for peripheral in self.peripherals.values() {
-            diagnostics.extend(peripheral.validate(&Context::new()));
+            diagnostics.extend(peripheral.diagnostics(&Context::new(), self.bus.as_ref()));
         }
 
         // collect all entitlements
@@ -101,6 +217,16 @@ impl Hal {
                 .or_default()
                 .extend(peripheral.entitlements.clone());
 
+            // the reset line and clock gate are state paths as well
+            entitlements
+                .entry(context.clone())
+                .or_default()
+                .extend(peripheral.reset_line.clone());
+            entitlements
+                .entry(context.clone())
+                .or_default()
+                .extend(peripheral.clock_gated.clone());
+
             for register in peripheral.registers.values() {
                 let context = context.clone().and(register.module_name().to_string());
 
@@ -135,6 +261,19 @@ impl Hal {
             }
         }
 
+        // interrupt enable states are entitled to by the interrupt unmasking interface
+        for interrupt in self.interrupts.iter() {
+            if let InterruptKind::Handler(ident) = &interrupt.kind {
+                entitlements
+                    .entry(Context::with_path(vec![
+                        "interrupts".to_string(),
+                        ident.to_string(),
+                    ]))
+                    .or_default()
+                    .extend(interrupt.enable.clone());
+            }
+        }
+
         // traverse the hal tree given the entitlement path and ensure the path exists
         for (context, entitlements) in entitlements {
             for entitlement in entitlements {
@@ -144,6 +283,8 @@ impl Hal {
                             "entitlement peripheral [{}] does not exist",
                             entitlement.peripheral().to_string().bold()
                         ))
+                        .with_code("entitlement-peripheral-missing")
+                        .with_location(entitlement.location().clone())
                         .with_context(context.clone()),
                     );
 
@@ -156,6 +297,8 @@ impl Hal {
                             "entitlement register [{}] does not exist",
                             entitlement.register().to_string().bold()
                         ))
+                        .with_code("entitlement-register-missing")
+                        .with_location(entitlement.location().clone())
                         .with_context(context.clone()),
                     );
 
@@ -168,6 +311,8 @@ impl Hal {
                             "entitlement field [{}] does not exist",
                             entitlement.field().to_string().bold()
                         ))
+                        .with_code("entitlement-field-missing")
+                        .with_location(entitlement.location().clone())
                         .with_context(context.clone()),
                     );
 
@@ -181,6 +326,8 @@ impl Hal {
                             entitlement.to_string().bold(),
                             entitlement.field().to_string().bold()
                         ))
+                            .with_code("entitlement-unresolvable")
+                            .with_location(entitlement.location().clone())
                             .with_context(context.clone()),
                     );
 
@@ -190,6 +337,8 @@ impl Hal {
                 let Numericity::Enumerated { variants } = &read.numericity else {
                     diagnostics.insert(
                         Diagnostic::error(format!("entitlement path [{}] targets numeric field which cannot be entitled to", entitlement.to_string().bold()))
+                            .with_code("entitlement-numeric")
+                            .with_location(entitlement.location().clone())
                             .with_context(context.clone()),
                     );
 
@@ -202,6 +351,8 @@ impl Hal {
                             "entitlement variant [{}] does not exist",
                             entitlement.variant().to_string().bold()
                         ))
+                        .with_code("entitlement-variant-missing")
+                        .with_location(entitlement.location().clone())
                         .with_context(context.clone()),
                     );
 
@@ -211,6 +362,288 @@ impl Hal {
         }
 
         diagnostics.extend(self.interrupts.validate());
+        diagnostics.extend(self.interrupt_association_diagnostics());
+
+        for peripheral in self.peripherals.values() {
+            for register in peripheral.registers.values() {
+                for field in register.fields.values() {
+                    let Some(interrupt) = &field.wake_from else {
+                        continue;
+                    };
+
+                    if !self.interrupts.iter().any(|candidate| {
+                        matches!(&candidate.kind, InterruptKind::Handler(ident) if ident == interrupt)
+                    }) {
+                        diagnostics.insert(
+                            Diagnostic::error(format!(
+                                "field is woken from [{}] which is not an interrupt of the HAL",
+                                interrupt.to_string().bold()
+                            ))
+                            .with_code("unknown-interrupt")
+                            .with_location(field.location.clone())
+                            .with_context(Context::with_path(vec![
+                                peripheral.module_name().to_string(),
+                                register.module_name().to_string(),
+                                field.module_name().to_string(),
+                            ])),
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some(bus) = &self.bus {
+            diagnostics.extend(self.bus_diagnostics(bus));
+        }
+
+        diagnostics
+    }
+
+    /// Ensure the peripherals and fields interrupts are associated with exist.
+    fn interrupt_association_diagnostics(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        for interrupt in self.interrupts.iter() {
+            let InterruptKind::Handler(ident) = &interrupt.kind else {
+                continue;
+            };
+
+            let context = Context::with_path(vec!["interrupts".to_string(), ident.to_string()]);
+
+            for source in &interrupt.sources {
+                if !self.peripherals.contains_key(source) {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "interrupt source [{}] does not exist",
+                            source.to_string().bold()
+                        ))
+                        .with_code("interrupt-source-missing")
+                        .with_location(interrupt.location.clone())
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+
+            for path in [&interrupt.enable, &interrupt.status].into_iter().flatten() {
+                if !interrupt.sources.contains(path.peripheral()) {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "interrupt field [{}] does not reside in a source of the interrupt",
+                            path.to_string().bold()
+                        ))
+                        .with_code("interrupt-field-foreign")
+                        .with_location(path.location().clone())
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+
+            // the status field is written by hardware, so it need not be resolvable (unlike the enable field)
+            let Some(status) = &interrupt.status else {
+                continue;
+            };
+
+            let exists = self
+                .peripherals
+                .get(status.peripheral())
+                .and_then(|peripheral| peripheral.registers.get(status.register()))
+                .and_then(|register| register.fields.get(status.field()))
+                .and_then(|field| field.access.get_read())
+                .is_some_and(|read| {
+                    matches!(&read.numericity, Numericity::Enumerated { variants } if variants.contains_key(status.variant()))
+                });
+
+            if !exists {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "interrupt status [{}] does not exist or is not readable",
+                        status.to_string().bold()
+                    ))
+                    .with_code("interrupt-status-missing")
+                    .with_location(status.location().clone())
+                    .with_context(context.clone()),
+                );
+            }
+        }
+
+        diagnostics
+    }
+
+    fn memory_diagnostics(&self) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        if self.memory.is_empty() {
+            return diagnostics;
+        }
+
+        let context = Context::with_path(vec!["memory".to_string()]);
+
+        let mut namespace = Namespace::new("memory module");
+
+        for region in &self.memory {
+            namespace.insert(
+                "region",
+                &region.ident,
+                &region.location,
+                naming::screaming_snake_case,
+            );
+        }
+
+        diagnostics.extend(namespace.validate(&context));
+
+        let mut regions = self.memory.iter().collect::<Vec<_>>();
+        regions.sort_by_key(|region| region.origin);
+
+        // a region may overlap any later region which begins before it ends, not only its neighbour
+        for (i, lhs) in regions.iter().enumerate() {
+            for rhs in regions[i + 1..]
+                .iter()
+                .take_while(|rhs| u64::from(rhs.origin) < lhs.end())
+            {
+                if lhs.overlaps(u64::from(rhs.origin), rhs.end()) {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "memory regions [{}] and [{}] overlap.",
+                            lhs.ident, rhs.ident
+                        ))
+                        .with_code("memory-overlap")
+                        .with_location(rhs.location.clone())
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+        }
+
+        // the peripherals of bus-backed devices are not in the memory map
+        if self.bus.is_none() {
+            for peripheral in self.peripherals.values() {
+                let start = u64::from(peripheral.base_addr);
+                let end = start + u64::from(peripheral.width());
+
+                if start == end {
+                    continue;
+                }
+
+                for region in regions.iter().filter(|region| region.overlaps(start, end)) {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "peripheral [{}] lies within memory region [{}].",
+                            peripheral.ident, region.ident
+                        ))
+                        .with_code("peripheral-in-memory")
+                        .with_location(peripheral.location.clone())
+                        .with_context(Context::new().and(peripheral.module_name().to_string())),
+                    );
+                }
+            }
+        }
+
+        if self.interrupts.runtime == Runtime::CortexMRt {
+            for required in ["FLASH", "RAM"] {
+                if !self
+                    .memory
+                    .iter()
+                    .any(|region| region.const_name() == required)
+                {
+                    diagnostics.insert(
+                        Diagnostic::warning(format!(
+                            "cortex-m-rt requires a memory region named [{required}]."
+                        ))
+                        .with_code("memory-runtime-region")
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    fn bus_diagnostics(&self, bus: &Bus) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        let mut registers = self
+            .peripherals
+            .values()
+            .flat_map(|peripheral| {
+                peripheral.registers.values().map(move |register| {
+                    (
+                        u64::from(peripheral.base_addr) + u64::from(register.offset),
+                        peripheral,
+                        register,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        registers.sort_by_key(|(address, peripheral, register)| {
+            (*address, peripheral.ident.clone(), register.ident.clone())
+        });
+
+        for window in registers.windows(2) {
+            let (lhs_address, lhs_peripheral, lhs) = window[0];
+            let (rhs_address, rhs_peripheral, rhs) = window[1];
+
+            if lhs_address == rhs_address {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "registers [{}::{}] and [{}::{}] share device address 0x{rhs_address:x}.",
+                        lhs_peripheral.ident, lhs.ident, rhs_peripheral.ident, rhs.ident
+                    ))
+                    .with_code("bus-address-collision")
+                    .with_location(rhs.location.clone())
+                    .with_context(Context::with_path(vec![rhs_peripheral.ident.to_string()])),
+                );
+            }
+        }
+
+        let bits = u32::from(bus.word.bits());
+
+        for (.., peripheral, register) in &registers {
+            let context = Context::new()
+                .and(peripheral.ident.to_string())
+                .and(register.ident.to_string());
+
+            for field in register.fields.values() {
+                if u32::from(field.offset) + u32::from(field.width) > bits {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "field [{}] exceeds the {bits}-bit bus word.",
+                            field.module_name().to_string().bold()
+                        ))
+                        .with_code("bus-word-exceeded")
+                        .with_location(field.location.clone())
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+
+            for field in register
+                .fields
+                .values()
+                .filter(|field| field.wake_from.is_some())
+            {
+                diagnostics.insert(
+                    Diagnostic::error("bus-backed fields cannot be waited for")
+                        .with_code("wake-unwaitable")
+                        .with_location(field.location.clone())
+                        .with_context(context.clone()),
+                );
+            }
+
+            if let Some(reset) = register.reset
+                && bits < 32
+                && reset >> bits != 0
+            {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "reset value 0x{reset:x} exceeds the {bits}-bit bus word."
+                    ))
+                    .with_code("bus-word-exceeded")
+                    .with_location(register.location.clone())
+                    .with_context(context.clone()),
+                );
+            }
+        }
 
         diagnostics
     }
@@ -218,7 +651,27 @@ impl Hal {
 
 // codegen
 impl Hal {
-    fn generate_peripherals<'a>(peripherals: impl Iterator<Item = &'a Peripheral>) -> TokenStream {
+    fn generate_peripherals<'a>(
+        peripherals: impl Iterator<Item = &'a Peripheral>,
+        interrupts: &Interrupts,
+        bus: Option<&Bus>,
+    ) -> TokenStream {
+        let peripherals = peripherals.map(|peripheral| {
+            let interrupts = interrupts
+                .iter()
+                .filter_map(|interrupt| match &interrupt.kind {
+                    InterruptKind::Handler(ident)
+                        if interrupt.sources.contains(&peripheral.ident) =>
+                    {
+                        Some(ident.clone())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            peripheral.generate(bus, &interrupts)
+        });
+
         quote! {
             #(
                 #peripherals
@@ -226,8 +679,309 @@ impl Hal {
         }
     }
 
+    fn generate_simulator<'a>(peripherals: impl Iterator<Item = &'a Peripheral>) -> TokenStream {
+        let peripheral_idents = peripherals.map(|peripheral| peripheral.module_name());
+
+        quote! {
+            /// Create a simulator of the HAL's register file.
+            #[cfg(feature = "sim")]
+            pub fn simulator() -> ::proto_hal::sim::Simulator {
+                ::proto_hal::sim::Simulator::new(&[#(#peripheral_idents::MODEL,)*])
+            }
+        }
+    }
+
+    fn generate_wake<'a>(peripherals: impl Iterator<Item = &'a Peripheral>) -> Option<TokenStream> {
+        let mut wakers = BTreeMap::<String, Vec<TokenStream>>::new();
+
+        for peripheral in peripherals {
+            for register in peripheral.registers.values() {
+                for field in register.fields.values() {
+                    if let Some(interrupt) = &field.wake_from {
+                        let peripheral = peripheral.module_name();
+                        let register = register.module_name();
+                        let field = field.module_name();
+
+                        wakers
+                            .entry(interrupt.to_string())
+                            .or_default()
+                            .push(quote! { super::#peripheral::#register::#field::WAKER });
+                    }
+                }
+            }
+        }
+
+        if wakers.is_empty() {
+            None?
+        }
+
+        let interrupts = wakers
+            .keys()
+            .map(|interrupt| Ident::new(interrupt, Span::call_site()));
+        let wakers = wakers.values();
+
+        Some(quote! {
+            /// Wake tasks waiting on fields signaled by an interrupt.
+            ///
+            /// Call the function of an interrupt from its handler. The handler remains responsible for
+            /// clearing (or masking) the interrupt source.
+            pub mod wake {
+                #(
+                    #[allow(non_snake_case)]
+                    pub fn #interrupts() {
+                        #(#wakers.wake();)*
+                    }
+                )*
+            }
+        })
+    }
+
+    fn generate_reset_mocks<'a>(peripherals: impl Iterator<Item = &'a Peripheral>) -> TokenStream {
+        let peripheral_idents = peripherals.map(|peripheral| peripheral.module_name());
+
+        quote! {
+            /// Restore the mock storage of all peripherals to the reset state.
+            #[cfg(feature = "test-support")]
+            pub fn reset_mocks() {
+                #(
+                    #peripheral_idents::reset_mock();
+                )*
+            }
+        }
+    }
+
+    /// Descriptors of the memory regions.
+    fn generate_memory(&self) -> Option<TokenStream> {
+        if self.memory.is_empty() {
+            None?
+        }
+
+        let regions = &self.memory;
+
+        Some(quote! {
+            /// The regions of the device memory map.
+            pub mod memory {
+                #(#regions)*
+            }
+        })
+    }
+
+    /// The linker script which provides default handlers for the interrupts (if any).
+    ///
+    /// *Note: The interrupts of bus-backed devices are not in the vector table, so no script is
+    /// produced.*
+    pub fn device_x(&self) -> Option<String> {
+        if self.bus.is_some() {
+            None?
+        }
+
+        self.interrupts.device_x()
+    }
+
+    /// The linker script which declares the memory regions (if any are described).
+    pub fn memory_x(&self) -> Option<String> {
+        if self.memory.is_empty() {
+            None?
+        }
+
+        Some(memory::memory_x(&self.memory))
+    }
+
+    /// The device contract of the core (as expected by RTIC).
+    fn generate_device(&self) -> Option<TokenStream> {
+        let cpu = self.cpu.as_ref()?;
+
+        let interrupt_number = (!self.interrupts.is_empty()).then(|| {
+            quote! {
+                #[cfg(feature = "interrupts")]
+                pub use interrupt as Interrupt;
+
+                // SAFETY: the discriminants are the positions of the interrupts in the vector table.
+                #[cfg(feature = "interrupts")]
+                unsafe impl ::cortex_m::interrupt::InterruptNumber for interrupt {
+                    fn number(self) -> u16 {
+                        self as u16
+                    }
+                }
+            }
+        });
+
+        Some(quote! {
+            #cpu
+            #interrupt_number
+        })
+    }
+
+    /// Type-safe NVIC masking of the interrupts, and binding of resources to their handlers.
+    ///
+    /// The masks are the only writers of the NVIC set/clear-enable bits (whose states are not
+    /// tracked by the NVIC model), so every handler has one.
+    ///
+    /// *Note: Interrupts of bus-backed devices and of runtimes other than cortex-m-rt are not routed
+    /// through the NVIC, so none are generated.*
+    fn generate_interrupt_masks(&self) -> Option<TokenStream> {
+        if self.bus.is_some() || !self.interrupts.runtime.nvic() {
+            None?
+        }
+
+        let mut interrupt_idents = Vec::new();
+        let mut interrupts = Vec::new();
+
+        for (position, interrupt) in self.interrupts.iter().enumerate() {
+            let InterruptKind::Handler(ident) = &interrupt.kind else {
+                continue;
+            };
+
+            let position = position as u16;
+
+            // the source peripherals must be unmasked, and the interrupt request enabled
+            let mut entitlements = interrupt
+                .sources
+                .iter()
+                .filter_map(|source| self.peripherals.get(source))
+                .flat_map(|peripheral| peripheral.entitlements.iter())
+                .chain(&interrupt.enable)
+                .collect::<Vec<_>>();
+            entitlements.sort_by_key(|entitlement| entitlement.to_string());
+            entitlements.dedup();
+
+            let entitlement_idents = entitlements
+                .iter()
+                .enumerate()
+                .map(|(i, ..)| format_ident!("entitlement_{i}"))
+                .collect::<Vec<_>>();
+            let entitlement_paths = entitlements
+                .iter()
+                .map(|entitlement| entitlement.render())
+                .collect::<Vec<_>>();
+
+            let docs = &interrupt.docs;
+
+            interrupts.push(quote! {
+                #(#[doc = #docs])*
+                #[allow(non_snake_case)]
+                pub mod #ident {
+                    /// The position of the interrupt in the vector table.
+                    pub const POSITION: u16 = #position;
+
+                    /// The interrupt is masked in the NVIC.
+                    pub struct Masked {
+                        _sealed: (),
+                    }
+
+                    /// The interrupt is unmasked in the NVIC.
+                    pub struct Unmasked {
+                        #(
+                            #entitlement_idents: ::proto_hal::stasis::Entitlement<#entitlement_paths>,
+                        )*
+
+                        _sealed: (),
+                    }
+
+                    impl Masked {
+                        /// # Safety
+                        /// TODO: link to conjure docs.
+                        pub unsafe fn conjure() -> Self {
+                            Self {
+                                _sealed: (),
+                            }
+                        }
+
+                        /// Unmask the interrupt in the NVIC.
+                        ///
+                        /// The entitlements witness that the source peripherals are unmasked and the
+                        /// interrupt request is enabled.
+                        ///
+                        /// # Safety
+                        /// Unmasking an interrupt may break critical sections based on masking the interrupt.
+                        pub unsafe fn unmask(self, #(#entitlement_idents: impl Into<::proto_hal::stasis::Entitlement<#entitlement_paths>>),*) -> Unmasked {
+                            unsafe { ::proto_hal::interrupt::unmask(POSITION) };
+
+                            Unmasked {
+                                #(#entitlement_idents: #entitlement_idents.into(),)*
+                                _sealed: (),
+                            }
+                        }
+
+                        /// Move `resources` into the handler context of `binding`, then unmask the interrupt.
+                        ///
+                        /// Resources still bound to the handler (from being masked rather than unbound) are
+                        /// returned.
+                        ///
+                        /// # Safety
+                        /// Unmasking an interrupt may break critical sections based on masking the interrupt.
+                        pub unsafe fn bind<R: Send>(self, binding: &::proto_hal::interrupt::Binding<Masked, R>, resources: R, #(#entitlement_idents: impl Into<::proto_hal::stasis::Entitlement<#entitlement_paths>>),*) -> (Unmasked, Option<R>) {
+                            let displaced = binding.bind(&self, resources);
+
+                            (unsafe { self.unmask(#(#entitlement_idents),*) }, displaced)
+                        }
+                    }
+
+                    impl Unmasked {
+                        /// Mask the interrupt in the NVIC, releasing the entitlements it was unmasked with.
+                        pub fn mask(self) -> (Masked, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*) {
+                            ::proto_hal::interrupt::mask(POSITION);
+
+                            (
+                                Masked {
+                                    _sealed: (),
+                                },
+                                #(
+                                    self.#entitlement_idents,
+                                )*
+                            )
+                        }
+
+                        /// Mask the interrupt, then move the resources out of the handler context of `binding`.
+                        pub fn unbind<R: Send>(self, binding: &::proto_hal::interrupt::Binding<Masked, R>) -> (Masked, Option<R>, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*) {
+                            let (masked, #(#entitlement_idents,)*) = self.mask();
+
+                            let resources = binding.unbind(&masked);
+
+                            (masked, resources, #(#entitlement_idents,)*)
+                        }
+                    }
+                }
+            });
+            interrupt_idents.push(ident);
+        }
+
+        if interrupts.is_empty() {
+            None?
+        }
+
+        Some(quote! {
+            /// NVIC masking of the interrupts raised by peripherals.
+            pub mod interrupts {
+                #(#interrupts)*
+
+                /// The NVIC mask states of the interrupts.
+                #[allow(non_snake_case)]
+                pub struct Masked {
+                    #(
+                        pub #interrupt_idents: #interrupt_idents::Masked,
+                    )*
+                }
+
+                impl Masked {
+                    /// # Safety
+                    /// TODO: link to conjure docs.
+                    pub unsafe fn conjure() -> Self {
+                        Self {
+                            #(
+                                #interrupt_idents: unsafe { #interrupt_idents::Masked::conjure() },
+                            )*
+                        }
+                    }
+                }
+            }
+        })
+    }
+
     fn generate_peripherals_struct<'a>(
         peripherals: impl Iterator<Item = &'a Peripheral> + Clone,
+        interrupts: bool,
+        reset_on_startup: bool,
     ) -> TokenStream {
         let fundamental_peripheral_idents = peripherals
             .clone()
@@ -250,6 +1004,15 @@ impl Hal {
             })
             .collect::<Vec<_>>();
 
+        let (interrupts_field, interrupts_conjure) = if interrupts {
+            (
+                Some(quote! { pub interrupts: interrupts::Masked, }),
+                Some(quote! { interrupts: unsafe { interrupts::Masked::conjure() }, }),
+            )
+        } else {
+            (None, None)
+        };
+
         quote! {
             pub struct Peripherals {
                 // fundamental
@@ -261,6 +1024,74 @@ impl Hal {
                 #(
                     pub #conditional_peripheral_idents: #conditional_peripheral_idents::Masked,
                 )*
+
+                #interrupts_field
+            }
+
+            static TAKEN: ::core::sync::atomic::AtomicBool = ::core::sync::atomic::AtomicBool::new(false);
+            static RESET_ON_STARTUP: ::core::sync::atomic::AtomicBool = ::core::sync::atomic::AtomicBool::new(#reset_on_startup);
+
+            impl Peripherals {
+                /// Take the peripherals if they have not been taken already.
+                ///
+                /// The peripherals are only available if the HAL declares them to be in the reset state
+                /// on startup, or once the application has declared so with
+                /// [`declare_reset_on_startup`](Self::declare_reset_on_startup).
+                pub fn take() -> Result<Self, ::proto_hal::TakeError> {
+                    claim()?;
+
+                    Ok(unsafe { Self::steal() })
+                }
+
+                /// Declare that the peripherals are in the reset state, allowing them to be
+                /// [taken](Self::take).
+                ///
+                /// # Safety
+                /// The peripherals must be in the reset state (i.e. the startup routine does not hand
+                /// over modified peripherals), and must not have been accessed before this call.
+                pub unsafe fn declare_reset_on_startup() {
+                    RESET_ON_STARTUP.store(true, ::core::sync::atomic::Ordering::Relaxed);
+                }
+
+                /// Unconditionally create the peripherals, regardless of whether they have been taken.
+                ///
+                /// # Safety
+                /// This function assumes and requires all of the following:
+                /// 1. The peripherals are in the reset state.
+                /// 1. The peripherals are not accessed anywhere else.
+                pub unsafe fn steal() -> Self {
+                    #[allow(unsafe_op_in_unsafe_fn)]
+                    Self {
+                        // fundamental
+                        #(
+                            #fundamental_peripheral_idents: unsafe { #fundamental_peripheral_idents::Reset::conjure() },
+                        )*
+
+                        // conditional
+                        #(
+                            #conditional_peripheral_idents: unsafe { #conditional_peripheral_idents::Masked::conjure() },
+                        )*
+
+                        #interrupts_conjure
+                    }
+                }
+            }
+
+            /// Mark the peripherals as taken if they are available.
+            fn claim() -> Result<(), ::proto_hal::TakeError> {
+                ::proto_hal::critical_section::with(|_| {
+                    if !RESET_ON_STARTUP.load(::core::sync::atomic::Ordering::Relaxed) {
+                        Err(::proto_hal::TakeError::Undeclared)?
+                    }
+
+                    if TAKEN.load(::core::sync::atomic::Ordering::Relaxed) {
+                        Err(::proto_hal::TakeError::Taken)?
+                    }
+
+                    TAKEN.store(true, ::core::sync::atomic::Ordering::Relaxed);
+
+                    Ok(())
+                })
             }
 
             /// # Safety
@@ -269,17 +1100,65 @@ impl Hal {
             /// 1. The peripherals are not accessed anywhere else.
             ///
             /// These invariances can be easyily achieved by limiting the call-site of this function to one place.
+            ///
+            /// *Note: This is equivalent to [`Peripherals::steal`].*
             pub unsafe fn peripherals() -> Peripherals {
-                #[allow(unsafe_op_in_unsafe_fn)]
-                Peripherals {
+                unsafe { Peripherals::steal() }
+            }
+        }
+    }
+
+    fn generate_adopt<'a>(
+        peripherals: impl Iterator<Item = &'a Peripheral> + Clone,
+    ) -> TokenStream {
+        let fundamental_peripheral_idents = peripherals
+            .clone()
+            .filter(|peripheral| peripheral.entitlements.is_empty())
+            .map(|peripheral| peripheral.module_name())
+            .collect::<Vec<_>>();
+
+        let conditional_peripheral_idents = peripherals
+            .filter(|peripheral| !peripheral.entitlements.is_empty())
+            .map(|peripheral| peripheral.module_name())
+            .collect::<Vec<_>>();
+
+        let report = if fundamental_peripheral_idents.is_empty() {
+            quote! { #[expect(unused)] report }
+        } else {
+            quote! { report }
+        };
+
+        quote! {
+            pub struct AdoptedPeripherals {
+                // fundamental
+                #(
+                    pub #fundamental_peripheral_idents: #fundamental_peripheral_idents::Adopted,
+                )*
+
+                // conditional
+                #(
+                    pub #conditional_peripheral_idents: #conditional_peripheral_idents::Unadopted,
+                )*
+            }
+
+            /// Read the live registers and reconstruct the peripheral states from them, rather than
+            /// assuming the reset state (such as after a bootloader handoff).
+            ///
+            /// Fields holding values which do not correspond to any variant are reported. Conditional
+            /// peripherals are adopted once their entitlements are proven.
+            ///
+            /// # Safety
+            /// This function assumes and requires that the peripherals are not accessed anywhere else.
+            pub unsafe fn adopt(#report: &mut impl FnMut(::proto_hal::adopt::Unmodeled)) -> AdoptedPeripherals {
+                AdoptedPeripherals {
                     // fundamental
                     #(
-                        #fundamental_peripheral_idents: unsafe { #fundamental_peripheral_idents::Reset::conjure() },
+                        #fundamental_peripheral_idents: unsafe { #fundamental_peripheral_idents::Adopted::adopt(report) },
                     )*
 
                     // conditional
                     #(
-                        #conditional_peripheral_idents: unsafe { #conditional_peripheral_idents::Masked::conjure() },
+                        #conditional_peripheral_idents: unsafe { #conditional_peripheral_idents::Unadopted::conjure() },
                     )*
                 }
             }
@@ -287,10 +1166,61 @@ impl Hal {
     }
 }
 
-impl ToTokens for Hal {
+impl Validated<Hal> {
+    pub fn render_raw(&self) -> String {
+        self.to_token_stream().to_string()
+    }
+
+    pub fn render(&self) -> Result<String, String> {
+        let content = self.to_token_stream().to_string();
+        let parsed = syn::parse_file(content.as_str());
+
+        match parsed {
+            Ok(file) => Ok(prettyplease::unparse(&file)),
+            Err(e) => {
+                // the token stream is rendered as a single line, so the column is an index into it
+                let start = e.span().start().column.min(content.len());
+                let end = e.span().end().column.clamp(start, content.len());
+
+                const PADDING: usize = 50;
+
+                let excerpt = |from: usize, to: usize| content.get(from..to).unwrap_or_default();
+
+                let lhs = excerpt(start.saturating_sub(PADDING), start);
+                let err = excerpt(start, end).red();
+                let rhs = excerpt(end, end.saturating_add(PADDING).min(content.len()));
+
+                Err(format!("{}:\n{lhs}{err}{rhs}", e))
+            }
+        }
+    }
+}
+
+impl ToTokens for Validated<Hal> {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        tokens.extend(Self::generate_peripherals(self.peripherals.values()));
-        tokens.extend(Self::generate_peripherals_struct(self.peripherals.values()));
-        self.interrupts.to_tokens(tokens);
+        tokens.extend(Hal::generate_peripherals(
+            self.peripherals.values(),
+            &self.interrupts,
+            self.bus.as_ref(),
+        ));
+        let interrupt_masks = self.generate_interrupt_masks();
+
+        tokens.extend(Hal::generate_peripherals_struct(
+            self.peripherals.values(),
+            interrupt_masks.is_some(),
+            self.reset_on_startup,
+        ));
+        tokens.extend(interrupt_masks);
+        tokens.extend(Hal::generate_wake(self.peripherals.values()));
+        if self.bus.is_none() {
+            tokens.extend(Hal::generate_adopt(self.peripherals.values()));
+            tokens.extend(Hal::generate_simulator(self.peripherals.values()));
+            tokens.extend(Hal::generate_reset_mocks(self.peripherals.values()));
+        }
+        if self.bus.is_none() {
+            self.interrupts.to_tokens(tokens);
+        }
+        tokens.extend(self.generate_device());
+        tokens.extend(self.generate_memory());
     }
 }
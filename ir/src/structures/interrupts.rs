@@ -8,7 +8,13 @@ use proc_macro2::Span;
 use quote::{ToTokens, quote};
 use syn::{Ident, Index};
 
-use crate::utils::diagnostic::{Context, Diagnostic, Diagnostics};
+use crate::{
+    structures::entitlement::Entitlement,
+    utils::{
+        diagnostic::{Context, Diagnostic, Diagnostics, Location},
+        naming::Namespace,
+    },
+};
 
 #[derive(Debug, Clone)]
 pub enum InterruptKind {
@@ -19,21 +25,32 @@ pub enum InterruptKind {
 #[derive(Debug, Clone)]
 pub struct Interrupt {
     pub kind: InterruptKind,
+    pub sources: Vec<Ident>,
+    pub enable: Option<Entitlement>,
+    pub status: Option<Entitlement>,
     pub docs: Vec<String>,
+    pub location: Location,
 }
 
 impl Interrupt {
+    #[track_caller]
     pub fn new(kind: InterruptKind) -> Self {
         Self {
             kind,
+            sources: Vec::new(),
+            enable: None,
+            status: None,
             docs: Vec::new(),
+            location: Location::caller(),
         }
     }
 
+    #[track_caller]
     pub fn reserved() -> Self {
         Self::new(InterruptKind::Reserved)
     }
 
+    #[track_caller]
     pub fn handler(ident: impl AsRef<str>) -> Self {
         Self::new(InterruptKind::Handler(Ident::new(
             ident.as_ref(),
@@ -41,6 +58,33 @@ impl Interrupt {
         )))
     }
 
+    /// Associate the interrupt with the peripherals which raise it.
+    pub fn sources<I>(mut self, peripherals: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.sources.extend(
+            peripherals
+                .into_iter()
+                .map(|peripheral| Ident::new(peripheral.as_ref(), Span::call_site())),
+        );
+
+        self
+    }
+
+    /// Declare the state of the field which enables the interrupt request (e.g. `cordic::csr::ien::Enabled`).
+    pub fn enable(mut self, enabled: Entitlement) -> Self {
+        self.enable = Some(enabled);
+        self
+    }
+
+    /// Declare the state of the field which flags the interrupt request (e.g. `cordic::csr::rrdy::Ready`).
+    pub fn status(mut self, pending: Entitlement) -> Self {
+        self.status = Some(pending);
+        self
+    }
+
     pub fn docs<I>(mut self, docs: I) -> Self
     where
         I: IntoIterator,
@@ -53,9 +97,75 @@ impl Interrupt {
     }
 }
 
+/// The runtime which consumes the generated vector table and dispatches interrupts to handlers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Runtime {
+    /// Handlers are bound with `cortex_m_rt::interrupt` and the table is placed in
+    /// `.vector_table.interrupts`.
+    #[default]
+    CortexMRt,
+    /// Handlers are bound with `riscv_rt::external_interrupt`. The table is exported as
+    /// `__EXTERNAL_INTERRUPTS` and dispatched by `_dispatch_external_interrupt`, which the trap
+    /// handler of the PLIC/CLIC invokes with the claimed interrupt.
+    ///
+    /// *Note: The `interrupts` feature of the HAL must enable the `riscv` and `riscv-rt`
+    /// dependencies.*
+    RiscvRt,
+    /// No runtime is assumed. The table is exported as `__INTERRUPTS` for the application to
+    /// place, and handlers must be defined by the application.
+    Standalone,
+}
+
+impl Runtime {
+    /// Whether the runtime targets the Cortex-M NVIC.
+    pub fn nvic(&self) -> bool {
+        matches!(self, Self::CortexMRt)
+    }
+
+    /// The handler symbols the runtime defines for core exceptions.
+    fn exceptions(&self) -> &'static [&'static str] {
+        match self {
+            Self::CortexMRt => &[
+                "Reset",
+                "NonMaskableInt",
+                "HardFault",
+                "MemoryManagement",
+                "BusFault",
+                "UsageFault",
+                "SecureFault",
+                "SVCall",
+                "DebugMonitor",
+                "PendSV",
+                "SysTick",
+                "DefaultHandler",
+            ],
+            Self::RiscvRt => &[
+                "SupervisorSoft",
+                "MachineSoft",
+                "SupervisorTimer",
+                "MachineTimer",
+                "SupervisorExternal",
+                "MachineExternal",
+                "ExceptionHandler",
+                "DefaultHandler",
+            ],
+            Self::Standalone => &[],
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::CortexMRt => "cortex-m-rt vector table",
+            Self::RiscvRt => "riscv-rt external interrupt table",
+            Self::Standalone => "standalone vector table",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Interrupts {
     interrupts: Vec<Interrupt>,
+    pub runtime: Runtime,
 }
 
 impl Deref for Interrupts {
@@ -76,6 +186,7 @@ impl Interrupts {
     pub fn empty() -> Self {
         Self {
             interrupts: Vec::new(),
+            runtime: Runtime::default(),
         }
     }
 
@@ -84,8 +195,29 @@ impl Interrupts {
         let context = Context::with_path(vec!["interrupts".to_string()]);
 
         let mut seen = HashMap::new();
+        let mut namespace =
+            Namespace::new(self.runtime.description()).reserve(self.runtime.exceptions());
 
         for (i, interrupt) in self.interrupts.iter().enumerate() {
+            if let InterruptKind::Reserved = &interrupt.kind
+                && (!interrupt.sources.is_empty()
+                    || interrupt.enable.is_some()
+                    || interrupt.status.is_some())
+            {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "reserved interrupt at position {i} cannot be associated with peripherals or fields"
+                    ))
+                    .with_code("reserved-interrupt-associated")
+                    .with_location(interrupt.location.clone())
+                    .with_context(context.clone()),
+                );
+            }
+
+            if let InterruptKind::Handler(ident) = &interrupt.kind {
+                namespace.insert("interrupt", ident, &interrupt.location, str::to_string);
+            }
+
             if let InterruptKind::Handler(ident) = &interrupt.kind
                 && let Some(existing) = seen.insert(ident, i)
             {
@@ -94,6 +226,73 @@ impl Interrupts {
                         "interrupt [{}] at position {i} is already defined at position {existing}",
                         ident.to_string().bold()
                     ))
+                    .with_code("duplicate-interrupt")
+                    .with_location(interrupt.location.clone())
+                    .with_context(context.clone()),
+                );
+            }
+        }
+
+        diagnostics.extend(namespace.validate(&context));
+
+        diagnostics
+    }
+
+    /// Verify the position of each interrupt against the expected interrupt numbers
+    /// (e.g. as imported from a vendor SVD).
+    ///
+    /// Handlers absent from the expected interrupts are errors, while expected interrupts absent
+    /// from the table are warnings, as the model may intentionally reserve them (so they are
+    /// reported as advisories by `proto-hal-build`).
+    pub fn verify(&self, expected: &HashMap<String, usize>) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+        let context = Context::with_path(vec!["interrupts".to_string()]);
+
+        let mut positions = HashMap::new();
+
+        for (position, interrupt) in self.interrupts.iter().enumerate() {
+            let InterruptKind::Handler(ident) = &interrupt.kind else {
+                continue;
+            };
+            let name = ident.to_string();
+
+            match expected.get(&name) {
+                Some(&expected) if expected != position => {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "interrupt [{}] is at position {position} but is expected at position {expected}",
+                            name.bold()
+                        ))
+                        .with_code("interrupt-position-mismatch")
+                        .with_location(interrupt.location.clone())
+                        .with_context(context.clone()),
+                    );
+                }
+                Some(..) => {}
+                None => {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "interrupt [{}] at position {position} is not an expected interrupt",
+                            name.bold()
+                        ))
+                        .with_code("interrupt-unexpected")
+                        .with_location(interrupt.location.clone())
+                        .with_context(context.clone()),
+                    );
+                }
+            }
+
+            positions.insert(name, position);
+        }
+
+        for (name, position) in expected {
+            if !positions.contains_key(name) {
+                diagnostics.insert(
+                    Diagnostic::warning(format!(
+                        "expected interrupt [{}] at position {position} is not in the table",
+                        name.bold()
+                    ))
+                    .with_code("interrupt-missing")
                     .with_context(context.clone()),
                 );
             }
@@ -102,7 +301,14 @@ impl Interrupts {
         diagnostics
     }
 
-    pub fn device_x(&self) -> String {
+    /// The linker script which provides default handlers for the interrupts.
+    ///
+    /// *Note: The standalone runtime has no default handler, so no script is produced.*
+    pub fn device_x(&self) -> Option<String> {
+        if let Runtime::Standalone = self.runtime {
+            None?
+        }
+
         let mut body = String::new();
 
         for vector in self.interrupts.iter().filter_map(|interrupt| {
@@ -114,7 +320,7 @@ impl Interrupts {
             body.push_str(format!("PROVIDE({vector} = DefaultHandler);\n").as_str());
         }
 
-        body
+        Some(body)
     }
 }
 
@@ -153,11 +359,27 @@ impl ToTokens for Interrupts {
                 }
             });
 
+        let (runtime, section, table_ident, repr) = match self.runtime {
+            Runtime::CortexMRt => (
+                Some(quote! { pub use ::cortex_m_rt::interrupt; }),
+                Some(quote! { #[unsafe(link_section = ".vector_table.interrupts")] }),
+                quote! { __INTERRUPTS },
+                quote! { u16 },
+            ),
+            Runtime::RiscvRt => (
+                Some(quote! { pub use ::riscv_rt::external_interrupt as interrupt; }),
+                None,
+                quote! { __EXTERNAL_INTERRUPTS },
+                quote! { usize },
+            ),
+            Runtime::Standalone => (None, None, quote! { __INTERRUPTS }, quote! { u16 }),
+        };
+
         let table = quote! {
             #[doc(hidden)]
-            #[unsafe(link_section = ".vector_table.interrupts")]
+            #section
             #[unsafe(no_mangle)]
-            pub static __INTERRUPTS: [::proto_hal::interrupt::Vector; #table_length] = [
+            pub static #table_ident: [::proto_hal::interrupt::Vector; #table_length] = [
                 #(
                     #vectors,
                 )*
@@ -171,7 +393,36 @@ impl ToTokens for Interrupts {
             .filter_map(|(position, interrupt)| match &interrupt.kind {
                 InterruptKind::Reserved => None,
                 InterruptKind::Handler(ident) => {
-                    let docs = &interrupt.docs;
+                    let mut docs = interrupt.docs.clone();
+
+                    if !interrupt.sources.is_empty() {
+                        let sources = interrupt
+                            .sources
+                            .iter()
+                            .map(|peripheral| format!("[`{peripheral}`](crate::{peripheral})"))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+
+                        if !docs.is_empty() {
+                            docs.push(String::new());
+                        }
+                        docs.push(format!("Raised by {sources}."));
+                    }
+
+                    for (description, path) in [
+                        ("Enabled by", &interrupt.enable),
+                        ("Flagged by", &interrupt.status),
+                    ] {
+                        if let Some(path) = path {
+                            docs.push(format!(
+                                "{description} [`{path}`](crate::{}::{}::{}).",
+                                path.peripheral(),
+                                path.register(),
+                                path.field()
+                            ));
+                        }
+                    }
+
                     let position = Index::from(position);
                     Some(quote! {
                         #(#[doc = #docs])*
@@ -182,7 +433,8 @@ impl ToTokens for Interrupts {
 
         let enum_ = quote! {
             #[allow(non_camel_case_types)]
-            #[repr(u16)]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #[repr(#repr)]
             pub enum interrupt {
                 #(
                     #vectors
@@ -190,9 +442,14 @@ impl ToTokens for Interrupts {
             }
         };
 
+        if let Some(runtime) = runtime {
+            tokens.extend(quote! {
+                #[cfg(feature = "interrupts")]
+                #runtime
+            });
+        }
+
         tokens.extend(quote! {
-            #[cfg(feature = "interrupts")]
-            pub use ::cortex_m_rt::interrupt;
 
             #[cfg(feature = "interrupts")]
             #symbols
@@ -201,5 +458,61 @@ impl ToTokens for Interrupts {
             #[cfg(feature = "interrupts")]
             #enum_
         });
+
+        // the interrupt controller provides the position of the claimed interrupt, which is
+        // dispatched from the table
+        if let Runtime::RiscvRt = self.runtime {
+            let (handler_idents, positions): (Vec<_>, Vec<_>) = self
+                .interrupts
+                .iter()
+                .enumerate()
+                .filter_map(|(position, interrupt)| {
+                    let InterruptKind::Handler(ident) = &interrupt.kind else {
+                        None?
+                    };
+                    Some((ident, position))
+                })
+                .unzip();
+
+            let max_position = positions.iter().copied().max().unwrap_or_default();
+            let positions = positions.into_iter().map(Index::from);
+
+            tokens.extend(quote! {
+                #[cfg(feature = "interrupts")]
+                #[doc(hidden)]
+                #[unsafe(no_mangle)]
+                pub unsafe extern "C" fn _dispatch_external_interrupt(code: usize) {
+                    unsafe extern "C" {
+                        fn DefaultHandler();
+                    }
+
+                    unsafe {
+                        ::proto_hal::interrupt::dispatch(&__EXTERNAL_INTERRUPTS, code, DefaultHandler)
+                    };
+                }
+
+                // SAFETY: the discriminants are the positions of the interrupts in the table.
+                #[cfg(feature = "interrupts")]
+                unsafe impl ::riscv::InterruptNumber for interrupt {
+                    const MAX_INTERRUPT_NUMBER: usize = #max_position;
+
+                    fn number(self) -> usize {
+                        self as usize
+                    }
+
+                    fn from_number(number: usize) -> ::riscv::result::Result<Self> {
+                        match number {
+                            #(
+                                #positions => Ok(Self::#handler_idents),
+                            )*
+                            _ => Err(::riscv::result::Error::InvalidVariant(number)),
+                        }
+                    }
+                }
+
+                #[cfg(feature = "interrupts")]
+                unsafe impl ::riscv::ExternalInterruptNumber for interrupt {}
+            });
+        }
     }
 }
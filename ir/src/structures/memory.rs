@@ -0,0 +1,144 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, quote};
+use syn::Ident;
+
+use crate::utils::{diagnostic::Location, naming};
+
+/// The usage of a memory region by the linker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// Executable, read-only memory (e.g. flash).
+    Flash,
+    /// Executable, read-write memory (e.g. SRAM banks or CCM).
+    Ram,
+    /// Memory which is only read (e.g. option bytes or system memory).
+    ReadOnly,
+}
+
+impl RegionKind {
+    fn attributes(&self) -> &'static str {
+        match self {
+            Self::Flash => "rx",
+            Self::Ram => "rwx",
+            Self::ReadOnly => "r",
+        }
+    }
+}
+
+/// A region of the device memory map.
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub ident: Ident,
+    pub origin: u32,
+    pub length: u32,
+    pub kind: RegionKind,
+    pub docs: Vec<String>,
+    pub location: Location,
+}
+
+impl Region {
+    #[track_caller]
+    pub fn new(ident: impl AsRef<str>, origin: u32, length: u32, kind: RegionKind) -> Self {
+        Self {
+            ident: Ident::new(ident.as_ref(), Span::call_site()),
+            origin,
+            length,
+            kind,
+            docs: Vec::new(),
+            location: Location::caller(),
+        }
+    }
+
+    #[track_caller]
+    pub fn flash(ident: impl AsRef<str>, origin: u32, length: u32) -> Self {
+        Self::new(ident, origin, length, RegionKind::Flash)
+    }
+
+    #[track_caller]
+    pub fn ram(ident: impl AsRef<str>, origin: u32, length: u32) -> Self {
+        Self::new(ident, origin, length, RegionKind::Ram)
+    }
+
+    #[track_caller]
+    pub fn read_only(ident: impl AsRef<str>, origin: u32, length: u32) -> Self {
+        Self::new(ident, origin, length, RegionKind::ReadOnly)
+    }
+
+    pub fn docs<I>(mut self, docs: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.docs
+            .extend(docs.into_iter().map(|doc| doc.as_ref().to_string()));
+
+        self
+    }
+
+    /// The name of the region in generated code and linker scripts.
+    pub fn const_name(&self) -> Ident {
+        Ident::new(
+            naming::screaming_snake_case(self.ident.to_string().as_str()).as_str(),
+            Span::call_site(),
+        )
+    }
+
+    /// The address one past the end of the region.
+    pub fn end(&self) -> u64 {
+        u64::from(self.origin) + u64::from(self.length)
+    }
+
+    /// Whether the region overlaps the address range `start..end`.
+    pub fn overlaps(&self, start: u64, end: u64) -> bool {
+        u64::from(self.origin) < end && start < self.end()
+    }
+}
+
+impl ToTokens for Region {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = self.const_name();
+        let origin = self.origin as usize;
+        let length = self.length as usize;
+        let docs = &self.docs;
+
+        tokens.extend(quote! {
+            #(#[doc = #docs])*
+            pub const #ident: ::proto_hal::memory::Region = ::proto_hal::memory::Region {
+                origin: #origin,
+                length: #length,
+            };
+        });
+    }
+}
+
+/// The linker script which declares the memory regions.
+pub fn memory_x<'a>(regions: impl IntoIterator<Item = &'a Region>) -> String {
+    let mut body = String::from("MEMORY\n{\n");
+
+    for region in regions {
+        body.push_str(
+            format!(
+                "  {} ({}) : ORIGIN = {:#010x}, LENGTH = {}\n",
+                region.const_name(),
+                region.kind.attributes(),
+                region.origin,
+                length(region.length)
+            )
+            .as_str(),
+        );
+    }
+
+    body.push_str("}\n");
+
+    body
+}
+
+/// Render a length with the largest binary unit which divides it.
+fn length(length: u32) -> String {
+    match length {
+        0 => "0".to_string(),
+        _ if length.is_multiple_of(1024 * 1024) => format!("{}M", length / (1024 * 1024)),
+        _ if length.is_multiple_of(1024) => format!("{}K", length / 1024),
+        _ => length.to_string(),
+    }
+}
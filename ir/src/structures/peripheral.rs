@@ -1,23 +1,35 @@
 use std::collections::{HashMap, HashSet};
 
+use colored::Colorize;
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, format_ident, quote};
 use syn::{Ident, Path};
 
-use crate::utils::diagnostic::{Context, Diagnostic, Diagnostics};
+use crate::{
+    bus::{Bus, Interface},
+    utils::{
+        diagnostic::{Context, Diagnostic, Diagnostics, Location},
+        naming::{self, Namespace},
+    },
+};
 
-use super::{entitlement::Entitlement, register::Register};
+use super::{entitlement::Entitlement, field::Numericity, register::Register};
 
 #[derive(Debug, Clone)]
 pub struct Peripheral {
     pub ident: Ident,
     pub base_addr: u32,
     pub entitlements: HashSet<Entitlement>,
+    pub reset_line: Option<Entitlement>,
+    pub clock_enable: Option<Entitlement>,
+    pub clock_gated: Option<Entitlement>,
     pub registers: HashMap<Ident, Register>,
     pub docs: Vec<String>,
+    pub location: Location,
 }
 
 impl Peripheral {
+    #[track_caller]
     pub fn new(
         ident: impl AsRef<str>,
         base_addr: u32,
@@ -27,12 +39,16 @@ impl Peripheral {
             ident: Ident::new(ident.as_ref(), Span::call_site()),
             base_addr,
             entitlements: HashSet::new(),
+            reset_line: None,
+            clock_enable: None,
+            clock_gated: None,
             registers: HashMap::from_iter(
                 registers
                     .into_iter()
                     .map(|register| (register.ident.clone(), register)),
             ),
             docs: Vec::new(),
+            location: Location::caller(),
         }
     }
 
@@ -40,7 +56,7 @@ impl Peripheral {
         self.registers
             .values()
             .max_by(|lhs, rhs| lhs.offset.cmp(&rhs.offset))
-            .map(|register| register.offset + 4)
+            .map(|register| register.offset.saturating_add(4))
             .unwrap_or(0)
     }
 
@@ -49,6 +65,29 @@ impl Peripheral {
         self
     }
 
+    /// Declare the state of the reset line (such as an RCC reset bit) which holds the peripheral
+    /// in reset.
+    pub fn reset_line(mut self, asserted: Entitlement) -> Self {
+        self.reset_line = Some(asserted);
+        self
+    }
+
+    /// Declare the state of the clock-enable field which enables the peripheral's clock.
+    ///
+    /// Gating the clock reverts the registers, so the state is an entitlement of the peripheral.
+    pub fn clock_enable(mut self, enabled: Entitlement) -> Self {
+        self.entitlements.insert(enabled.clone());
+        self.clock_enable = Some(enabled);
+        self
+    }
+
+    /// Declare the state of the clock-enable field which gates the peripheral's clock, such that
+    /// a clock gate function is generated.
+    pub fn clock_gate(mut self, gated: Entitlement) -> Self {
+        self.clock_gated = Some(gated);
+        self
+    }
+
     pub fn docs<I>(mut self, docs: I) -> Self
     where
         I: IntoIterator,
@@ -66,42 +105,120 @@ impl Peripheral {
 
     pub fn type_name(&self) -> Ident {
         Ident::new(
-            inflector::cases::pascalcase::to_pascal_case(self.ident.to_string().as_str()).as_str(),
+            naming::pascal_case(self.ident.to_string().as_str()).as_str(),
             Span::call_site(),
         )
     }
 
+    /// Validate the peripheral as a memory-mapped peripheral.
     pub fn validate(&self, context: &Context) -> Diagnostics {
+        self.diagnostics(context, None)
+    }
+
+    /// Validate the peripheral, accessed through the bus if specified.
+    ///
+    /// *Note: Device register addresses are not subject to memory-mapped layout rules.*
+    pub(crate) fn diagnostics(&self, context: &Context, bus: Option<&Bus>) -> Diagnostics {
         let mut diagnostics = Diagnostics::new();
         let new_context = context.clone().and(self.ident.clone().to_string());
 
-        if self.base_addr % 4 != 0 {
+        if bus.is_none() {
+            if !self.base_addr.is_multiple_of(4) {
+                diagnostics.insert(
+                    Diagnostic::error("peripheral address must be word aligned.")
+                        .with_code("peripheral-misaligned")
+                        .with_location(self.location.clone())
+                        .with_context(new_context.clone()),
+                );
+            }
+
+            let mut sorted_registers = self.registers.values().collect::<Vec<_>>();
+            sorted_registers.sort_by_key(|register| register.offset);
+
+            for window in sorted_registers.windows(2) {
+                let lhs = window[0];
+                let rhs = window[1];
+
+                if lhs.offset.saturating_add(4) > rhs.offset {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "registers [{}] and [{}] overlap.",
+                            lhs.ident, rhs.ident
+                        ))
+                        .with_code("register-overlap")
+                        .with_location(rhs.location.clone())
+                        .with_context(new_context.clone()),
+                    );
+                }
+            }
+        }
+
+        let entitled_field = self.registers.values().find_map(|register| {
+            register
+                .fields
+                .values()
+                .find(|field| field.is_resolvable() && !field.entitlements.is_empty())
+        });
+
+        if let Some(reset_line) = &self.reset_line
+            && let Some(field) = entitled_field
+        {
             diagnostics.insert(
-                Diagnostic::error("peripheral address must be word aligned.")
-                    .with_context(new_context.clone()),
+                Diagnostic::error(format!(
+                    "the states of entitled field [{}] cannot be reverted by reset line [{}]",
+                    field.ident,
+                    reset_line.to_string().bold(),
+                ))
+                .notes([
+                    "the entitlements of the field would not be released by the reset, so either the reset line or the entitlements must be removed",
+                ])
+                .with_code("reset-line-entitled")
+                .with_location(reset_line.location().clone())
+                .with_context(new_context.clone()),
             );
         }
 
-        let mut sorted_registers = self.registers.values().collect::<Vec<_>>();
-        sorted_registers.sort_by(|lhs, rhs| lhs.offset.cmp(&rhs.offset));
+        if let Some(clock_gated) = &self.clock_gated
+            && let Some(field) = entitled_field
+        {
+            diagnostics.insert(
+                Diagnostic::error(format!(
+                    "the states of entitled field [{}] cannot be reverted by clock gate [{}]",
+                    field.ident,
+                    clock_gated.to_string().bold(),
+                ))
+                .notes([
+                    "the entitlements of the field would not be released by gating the clock, so either the clock gate or the entitlements must be removed",
+                ])
+                .with_code("clock-gate-entitled")
+                .with_location(clock_gated.location().clone())
+                .with_context(new_context.clone()),
+            );
+        }
 
-        for window in sorted_registers.windows(2) {
-            let lhs = window[0];
-            let rhs = window[1];
+        let mut namespace = Namespace::new("peripheral module").reserve([
+            "Reset",
+            "Masked",
+            "Entitlements",
+            "entitlements",
+            "Gating",
+            "Adopted",
+            "Unadopted",
+        ]);
 
-            if lhs.offset + 4 > rhs.offset {
-                diagnostics.insert(
-                    Diagnostic::error(format!(
-                        "registers [{}] and [{}] overlap.",
-                        lhs.ident, rhs.ident
-                    ))
-                    .with_context(new_context.clone()),
-                );
-            }
+        for register in self.registers.values() {
+            namespace.insert(
+                "register",
+                &register.ident,
+                &register.location,
+                str::to_string,
+            );
         }
 
+        diagnostics.extend(namespace.validate(&new_context));
+
         for register in self.registers.values() {
-            diagnostics.extend(register.validate(&new_context));
+            diagnostics.extend(register.diagnostics(&new_context, bus));
         }
 
         diagnostics
@@ -110,7 +227,12 @@ impl Peripheral {
 
 // codegen
 impl Peripheral {
-    fn generate_registers<'a>(registers: impl Iterator<Item = &'a Register>) -> TokenStream {
+    fn generate_registers<'a>(
+        registers: impl Iterator<Item = &'a Register>,
+        interface: Interface,
+    ) -> TokenStream {
+        let registers = registers.map(|register| register.generate(interface));
+
         quote! {
             #(
                 #registers
@@ -118,6 +240,19 @@ impl Peripheral {
         }
     }
 
+    /// Bus-backed devices are not memory-mapped, so the base address is only used to compute
+    /// device register addresses.
+    fn generate_bus_base_addr(base_addr: u32) -> TokenStream {
+        let base_addr_formatted = format!("0x{base_addr:08x}");
+
+        quote! {
+            #[doc = #base_addr_formatted]
+            pub const fn base_addr() -> usize {
+                #base_addr as _
+            }
+        }
+    }
+
     fn generate_base_addr(base_addr: u32, ident: &Ident) -> TokenStream {
         let base_addr_formatted = format!("0x{base_addr:08x}");
 
@@ -129,13 +264,13 @@ impl Peripheral {
         );
 
         quote! {
-            #[cfg(not(test))]
+            #[cfg(not(any(test, feature = "test-support")))]
             #[doc = #base_addr_formatted]
             pub const fn base_addr() -> usize {
                 #base_addr as _
             }
 
-            #[cfg(test)]
+            #[cfg(all(test, not(feature = "test-support")))]
             pub fn base_addr() -> usize {
                 unsafe extern "Rust" {
                     #[link_name = #link_symbol]
@@ -147,20 +282,125 @@ impl Peripheral {
         }
     }
 
-    fn generate_reset<'a>(
-        registers: impl Iterator<Item = &'a Register>,
+    /// Mock storage for host tests, sized to the peripheral and initialized with register reset values.
+    fn generate_mock<'a>(width: u32, registers: impl Iterator<Item = &'a Register>) -> TokenStream {
+        let mut words = vec![0u32; width.div_ceil(4) as usize];
+
+        for register in registers {
+            if let Some(reset) = register.reset {
+                words[(register.offset / 4) as usize] = reset;
+            }
+        }
+
+        let len = words.len();
+
+        quote! {
+            #[cfg(feature = "test-support")]
+            const MOCK_RESET: [u32; #len] = [#(#words,)*];
+
+            /// Mock storage standing in for the peripheral's memory in host tests.
+            #[cfg(feature = "test-support")]
+            pub static mut MOCK: [u32; #len] = MOCK_RESET;
+
+            #[cfg(feature = "test-support")]
+            pub fn base_addr() -> usize {
+                (&raw const MOCK).addr()
+            }
+
+            /// Restore the mock storage to the reset state.
+            #[cfg(feature = "test-support")]
+            pub fn reset_mock() {
+                unsafe { MOCK = MOCK_RESET };
+            }
+        }
+    }
+
+    fn generate_model<'a>(
+        ident: &Ident,
+        base_addr: u32,
+        registers: impl Iterator<Item = &'a Register> + Clone,
+    ) -> TokenStream {
+        let ident_str = ident.to_string();
+
+        // adoption reports unmodeled values of readable enumerated fields with the identifier
+        let reported = registers.clone().any(|register| {
+            register.fields.values().any(|field| {
+                field
+                    .access
+                    .get_read()
+                    .is_some_and(|read| matches!(read.numericity, Numericity::Enumerated { .. }))
+            })
+        });
+        let ident_cfg =
+            (!reported).then(|| quote! { #[cfg(any(feature = "sim", feature = "trace"))] });
+
+        let register_idents = registers.map(|register| register.module_name());
+
+        quote! {
+            #ident_cfg
+            const IDENT: &str = #ident_str;
+
+            #[cfg(feature = "sim")]
+            pub const MODEL: ::proto_hal::sim::PeripheralModel = ::proto_hal::sim::PeripheralModel {
+                ident: IDENT,
+                base_addr: #base_addr,
+                registers: &[#(#register_idents::MODEL,)*],
+            };
+        }
+    }
+
+    /// The entitlements a conditional peripheral was unmasked with.
+    fn generate_entitlements(
         entitlement_idents: &Vec<Ident>,
         entitlement_paths: &Vec<Path>,
+    ) -> Option<TokenStream> {
+        if entitlement_idents.is_empty() {
+            None?
+        }
+
+        Some(quote! {
+            /// The entitlements the peripheral was unmasked with.
+            pub struct Entitlements {
+                #(
+                    #entitlement_idents: ::proto_hal::stasis::Entitlement<#entitlement_paths>,
+                )*
+            }
+
+            impl Entitlements {
+                /// # Safety
+                /// TODO: link to conjure docs.
+                pub unsafe fn conjure() -> Self {
+                    #[allow(unsafe_op_in_unsafe_fn)]
+                    Self {
+                        #(
+                            #entitlement_idents: unsafe { <::proto_hal::stasis::Entitlement<#entitlement_paths> as ::proto_hal::stasis::Conjure>::conjure() },
+                        )*
+                    }
+                }
+            }
+        })
+    }
+
+    fn generate_reset<'a>(
+        registers: impl Iterator<Item = &'a Register>,
+        conditional: bool,
     ) -> TokenStream {
         let register_idents = registers
             .map(|register| register.module_name())
             .collect::<Vec<_>>();
 
+        let (entitlements_field, entitlements_conjure) = if conditional {
+            (
+                Some(quote! { pub entitlements: Entitlements, }),
+                Some(quote! { entitlements: unsafe { Entitlements::conjure() }, }),
+            )
+        } else {
+            (None, None)
+        };
+
         quote! {
             pub struct Reset {
-                #(
-                    #[expect(unused)] #entitlement_idents: ::proto_hal::stasis::Entitlement<#entitlement_paths>,
-                )*
+                #entitlements_field
 
                 #(
                     pub #register_idents: #register_idents::Reset,
@@ -173,26 +413,36 @@ impl Peripheral {
                 pub unsafe fn conjure() -> Self {
                     #[allow(unsafe_op_in_unsafe_fn)]
                     Self {
-                        #(
-                            #entitlement_idents: unsafe { <::proto_hal::stasis::Entitlement<#entitlement_paths> as ::proto_hal::stasis::Conjure>::conjure() },
-                        )*
+                        #entitlements_conjure
                         #(
                             #register_idents: unsafe { #register_idents::Reset::conjure() },
                         )*
                     }
                 }
             }
+
+            impl ::proto_hal::IntoReset for Reset {
+                type Reset = Reset;
+
+                fn into_reset(self) -> Self::Reset {
+                    self
+                }
+            }
         }
     }
 
-    fn generate_masked<'a>(
-        registers: impl Iterator<Item = &'a Register>,
+    fn generate_masked(
+        registers: &[&Register],
         entitlement_idents: &Vec<Ident>,
         entitlement_paths: &Vec<Path>,
+        interface: Interface,
     ) -> TokenStream {
-        let register_idents = registers.map(|register| register.module_name());
+        let register_idents = registers
+            .iter()
+            .map(|register| register.module_name())
+            .collect::<Vec<_>>();
 
-        quote! {
+        let mut out = quote! {
             pub struct Masked {
                 _sealed: (),
             }
@@ -209,28 +459,446 @@ impl Peripheral {
                 pub fn unmask(self, #(#entitlement_idents: impl Into<::proto_hal::stasis::Entitlement<#entitlement_paths>>),*) -> Reset {
                     unsafe {
                         Reset {
+                            entitlements: Entitlements {
+                                #(
+                                    #entitlement_idents: #entitlement_idents.into(),
+                                )*
+                            },
                             #(
-                                #entitlement_idents: #entitlement_idents.into(),
+                                #register_idents: #register_idents::Reset::conjure(),
                             )*
+                        }
+                    }
+                }
+            }
+
+            impl Reset {
+                /// Return the peripheral to the masked state, releasing the entitlements it was unmasked with.
+                ///
+                /// The registers are in the reset state, such that the peripheral can be unmasked again.
+                /// Use [`mask`] once the states of the registers have been moved out.
+                pub fn mask(self) -> (Masked, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*) {
+                    (
+                        Masked {
+                            _sealed: (),
+                        },
+                        #(
+                            self.entitlements.#entitlement_idents,
+                        )*
+                    )
+                }
+            }
+        };
+
+        // restoring the register states to reset requires writing through the unsafe interface
+        if interface.bus().is_none() {
+            let generics = registers
+                .iter()
+                .map(|register| {
+                    format_ident!(
+                        "{}States",
+                        naming::pascal_case(&register.module_name().to_string())
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            out.extend(quote! {
+                /// Return the peripheral to the masked state from the states of its registers, releasing the
+                /// entitlements it was unmasked with.
+                ///
+                /// The states of the registers are restored to reset, such that the peripheral can be
+                /// unmasked again.
+                pub fn mask<#(#generics,)*>(
+                    entitlements: Entitlements,
+                    #(
+                        #register_idents: #generics,
+                    )*
+                ) -> (Masked, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*)
+                where
+                    #(
+                        #generics: ::proto_hal::IntoReset<Reset = #register_idents::Reset>,
+                    )*
+                {
+                    #(
+                        ::proto_hal::IntoReset::into_reset(#register_idents);
+                    )*
+
+                    (
+                        Masked {
+                            _sealed: (),
+                        },
+                        #(
+                            entitlements.#entitlement_idents,
+                        )*
+                    )
+                }
+            });
+        } else if let Some((generics, params, bounds)) = Self::generate_fields_params(registers) {
+            // bus registers are not reset by consuming their states, so the reset values are written
+            // through the bus
+            let asyncness = interface.asyncness();
+            let generic = interface.generic();
+            let param = interface.param();
+            let arg = interface.arg();
+            let complete = interface.complete();
+            let ret = interface.ret(Some(
+                quote! { (Masked, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*) },
+            ));
+            let ok = interface.ok(Some(quote! {
+                (
+                    Masked {
+                        _sealed: (),
+                    },
+                    #(
+                        entitlements.#entitlement_idents,
+                    )*
+                )
+            }));
+
+            let writes = registers.iter().filter_map(|register| {
+                let register_ident = register.module_name();
+                let reset = register
+                    .reset
+                    .filter(|_| register.fields.values().any(|field| field.access.is_write()))?;
+
+                Some(quote! {
+                    unsafe { #register_ident::write_from_zero_untracked(#arg |w| { w.set_bits(#reset); w }) }#complete;
+                })
+            });
+
+            out.extend(quote! {
+                /// Return the peripheral to the masked state from the states of its fields, releasing the
+                /// entitlements it was unmasked with.
+                ///
+                /// The reset values of the registers are written through the bus, such that the peripheral
+                /// can be unmasked again.
+                pub #asyncness fn mask<#generic #(#generics,)*>(
+                    #param
+                    entitlements: Entitlements,
+                    #(#params,)*
+                ) #ret
+                where
+                    #(#bounds,)*
+                {
+                    #(#writes)*
+
+                    #ok
+                }
+            });
+        }
+
+        out
+    }
+
+    fn generate_adopted<'a>(
+        registers: impl Iterator<Item = &'a Register>,
+        entitlement_idents: &Vec<Ident>,
+        entitlement_paths: &Vec<Path>,
+    ) -> TokenStream {
+        let register_idents = registers
+            .map(|register| register.module_name())
+            .collect::<Vec<_>>();
+
+        let conditional = !entitlement_idents.is_empty();
+
+        let adopt = if !conditional {
+            quote! {
+                impl Adopted {
+                    /// Read the registers of the peripheral and reconstruct the states of their fields.
+                    ///
+                    /// Fields holding values which do not correspond to any variant are reported.
+                    ///
+                    /// # Safety
+                    /// The peripheral must not be accessed anywhere else.
+                    pub unsafe fn adopt(report: &mut impl FnMut(::proto_hal::adopt::Unmodeled)) -> Self {
+                        Self {
                             #(
-                                #register_idents: #register_idents::Reset::conjure(),
+                                #register_idents: unsafe { #register_idents::adopt(report) },
                             )*
                         }
                     }
                 }
             }
+        } else {
+            quote! {
+                /// A conditional peripheral awaiting adoption.
+                ///
+                /// The registers can only be read once the entitlements of the peripheral are proven.
+                pub struct Unadopted {
+                    _sealed: (),
+                }
+
+                impl Unadopted {
+                    /// # Safety
+                    /// TODO: link to conjure docs.
+                    pub unsafe fn conjure() -> Self {
+                        Self {
+                            _sealed: (),
+                        }
+                    }
+
+                    /// Read the registers of the peripheral and reconstruct the states of their fields.
+                    ///
+                    /// Fields holding values which do not correspond to any variant are reported.
+                    pub fn adopt(self, #(#entitlement_idents: impl Into<::proto_hal::stasis::Entitlement<#entitlement_paths>>,)* report: &mut impl FnMut(::proto_hal::adopt::Unmodeled)) -> Adopted {
+                        Adopted {
+                            entitlements: Entitlements {
+                                #(
+                                    #entitlement_idents: #entitlement_idents.into(),
+                                )*
+                            },
+                            #(
+                                #register_idents: unsafe { #register_idents::adopt(report) },
+                            )*
+                        }
+                    }
+                }
+            }
+        };
+
+        let (entitlements_field, entitlements_move) = if conditional {
+            (
+                Some(quote! { pub entitlements: Entitlements, }),
+                Some(quote! { entitlements: self.entitlements, }),
+            )
+        } else {
+            (None, None)
+        };
+
+        quote! {
+            /// The peripheral state reconstructed from the live registers.
+            pub struct Adopted {
+                #entitlements_field
+
+                #(
+                    pub #register_idents: #register_idents::Adopted,
+                )*
+            }
+
+            #adopt
+
+            impl ::proto_hal::IntoReset for Adopted {
+                type Reset = Reset;
+
+                fn into_reset(self) -> Self::Reset {
+                    Reset {
+                        #entitlements_move
+                        #(
+                            #register_idents: ::proto_hal::IntoReset::into_reset(self.#register_idents),
+                        )*
+                    }
+                }
+            }
         }
     }
 }
 
-impl ToTokens for Peripheral {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+impl Peripheral {
+    /// The parameters accepting the states of all fields of the registers, which are reverted
+    /// without being written.
+    ///
+    /// Returns the generics, parameters, and bounds, or nothing if the states of entitled fields
+    /// would be reverted, which validation rejects (see `reset-line-entitled`).
+    fn generate_fields_params(
+        registers: &[&Register],
+    ) -> Option<(Vec<Ident>, Vec<TokenStream>, Vec<TokenStream>)> {
+        let mut generics = Vec::new();
+        let mut params = Vec::new();
+        let mut bounds = Vec::new();
+
+        for register in registers {
+            if register.fields.is_empty() {
+                continue;
+            }
+
+            if register
+                .fields
+                .values()
+                .any(|field| field.is_resolvable() && !field.entitlements.is_empty())
+            {
+                None?
+            }
+
+            let register_ident = register.module_name();
+            let mut register_generics = Vec::new();
+
+            for field in register.fields.values() {
+                let field_ident = field.module_name();
+                let generic = format_ident!(
+                    "{}{}",
+                    naming::pascal_case(&register_ident.to_string()),
+                    field.type_name()
+                );
+
+                bounds.push(quote! {
+                    #generic: ::proto_hal::stasis::Outgoing<#register_ident::#field_ident::Field>
+                });
+                register_generics.push(generic);
+            }
+
+            params.push(quote! {
+                #[expect(unused)] #register_ident: #register_ident::Fields<#(#register_generics,)*>
+            });
+            generics.extend(register_generics);
+        }
+
+        Some((generics, params, bounds))
+    }
+
+    /// Reversion of the field states by the reset line.
+    fn generate_reset_peripheral(
+        registers: &[&Register],
+        reset_line: &Entitlement,
+        conditional: bool,
+    ) -> Option<TokenStream> {
+        let (generics, params, bounds) = Self::generate_fields_params(registers)?;
+
+        let register_idents = registers.iter().map(|register| register.module_name());
+        let shadowed_idents = registers
+            .iter()
+            .filter(|register| register.shadowed)
+            .map(|register| register.module_name());
+        let reset_line = reset_line.render();
+
+        let (entitlements_param, entitlements_field) = if conditional {
+            (
+                Some(quote! { entitlements: Entitlements, }),
+                Some(quote! { entitlements, }),
+            )
+        } else {
+            (None, None)
+        };
+
+        Some(quote! {
+            /// Revert the states of the peripheral's fields, which the asserted reset line holds in reset.
+            pub fn reset_peripheral<#(#generics,)*>(
+                #entitlements_param
+                #(#params,)*
+                #[expect(unused)] reset_line: &#reset_line,
+            ) -> Reset
+            where
+                #(#bounds,)*
+            {
+                #(
+                    #shadowed_idents::reset_shadow();
+                )*
+
+                Reset {
+                    #entitlements_field
+                    #(
+                        #register_idents: unsafe { #register_idents::Reset::conjure() },
+                    )*
+                }
+            }
+        })
+    }
+
+    /// Reversion of the field states by gating the clock.
+    fn generate_gate_clock(
+        registers: &[&Register],
+        clock_gated: &Entitlement,
+        entitlement_idents: &Vec<Ident>,
+        entitlement_paths: &Vec<Path>,
+    ) -> Option<TokenStream> {
+        let (generics, params, bounds) = Self::generate_fields_params(registers)?;
+
+        let shadowed_idents = registers
+            .iter()
+            .filter(|register| register.shadowed)
+            .map(|register| register.module_name());
+        let clock_gated = clock_gated.render();
+
+        Some(quote! {
+            /// A peripheral whose entitlements were released to gate its clock.
+            ///
+            /// The registers revert once the clock is gated, after which the peripheral is masked.
+            pub struct Gating {
+                _sealed: (),
+            }
+
+            impl Gating {
+                /// Return the peripheral to the masked state, now that its clock is gated.
+                pub fn gated(self, #[expect(unused)] clock_gated: &#clock_gated) -> Masked {
+                    #(
+                        #shadowed_idents::reset_shadow();
+                    )*
+
+                    Masked {
+                        _sealed: (),
+                    }
+                }
+            }
+
+            /// Release the entitlements the peripheral was unmasked with, such that its clock can be gated.
+            ///
+            /// Gating the clock reverts the states of the peripheral's fields.
+            pub fn gate_clock<#(#generics,)*>(
+                entitlements: Entitlements,
+                #(#params,)*
+            ) -> (Gating, #(::proto_hal::stasis::Entitlement<#entitlement_paths>,)*)
+            where
+                #(#bounds,)*
+            {
+                (
+                    Gating {
+                        _sealed: (),
+                    },
+                    #(
+                        entitlements.#entitlement_idents,
+                    )*
+                )
+            }
+        })
+    }
+
+    /// The interrupts raised by the peripheral.
+    fn generate_interrupts(interrupts: &[Ident]) -> Option<TokenStream> {
+        if interrupts.is_empty() {
+            None?
+        }
+
+        let len = interrupts.len();
+
+        let mut out = quote! {
+            /// The interrupts raised by the peripheral.
+            #[cfg(feature = "interrupts")]
+            pub const INTERRUPTS: [crate::interrupt; #len] = [#(crate::interrupt::#interrupts,)*];
+        };
+
+        if let [interrupt] = interrupts {
+            out.extend(quote! {
+                /// The interrupt raised by the peripheral.
+                #[cfg(feature = "interrupts")]
+                pub const INTERRUPT: crate::interrupt = crate::interrupt::#interrupt;
+            });
+        }
+
+        Some(out)
+    }
+
+    /// Generate the peripheral module, with register accesses performed through the bus if specified.
+    ///
+    /// `interrupts` are the interrupts the peripheral is a source of.
+    pub(crate) fn generate(&self, bus: Option<&Bus>, interrupts: &[Ident]) -> TokenStream {
         let mut body = quote! {};
 
         let ident = self.module_name();
 
-        body.extend(Self::generate_registers(self.registers.values()));
-        body.extend(Self::generate_base_addr(self.base_addr, &self.ident));
+        body.extend(Self::generate_registers(
+            self.registers.values(),
+            Interface::new(bus),
+        ));
+        if bus.is_some() {
+            body.extend(Self::generate_bus_base_addr(self.base_addr));
+        } else {
+            body.extend(Self::generate_base_addr(self.base_addr, &self.ident));
+            body.extend(Self::generate_mock(self.width(), self.registers.values()));
+            body.extend(Self::generate_model(
+                &ident,
+                self.base_addr,
+                self.registers.values(),
+            ));
+        }
 
         let entitlement_idents = self
             .entitlements
@@ -244,28 +912,68 @@ impl ToTokens for Peripheral {
             .map(|entitlement| entitlement.render())
             .collect::<Vec<_>>();
 
-        body.extend(Self::generate_reset(
-            self.registers.values(),
+        let conditional = !self.entitlements.is_empty();
+
+        body.extend(Self::generate_entitlements(
             &entitlement_idents,
             &entitlement_paths,
         ));
+        body.extend(Self::generate_reset(self.registers.values(), conditional));
 
-        if !self.entitlements.is_empty() {
-            body.extend(Self::generate_masked(
+        if bus.is_none() {
+            body.extend(Self::generate_adopted(
                 self.registers.values(),
                 &entitlement_idents,
                 &entitlement_paths,
             ));
         }
 
+        body.extend(Self::generate_interrupts(interrupts));
+
+        // the registers are passed in order of their offsets
+        let mut registers = self.registers.values().collect::<Vec<_>>();
+        registers.sort_by_key(|register| register.offset);
+
+        if let Some(reset_line) = &self.reset_line {
+            body.extend(Self::generate_reset_peripheral(
+                &registers,
+                reset_line,
+                conditional,
+            ));
+        }
+
+        if conditional {
+            body.extend(Self::generate_masked(
+                &registers,
+                &entitlement_idents,
+                &entitlement_paths,
+                Interface::new(bus),
+            ));
+        }
+
+        if let Some(clock_gated) = &self.clock_gated {
+            body.extend(Self::generate_gate_clock(
+                &registers,
+                clock_gated,
+                &entitlement_idents,
+                &entitlement_paths,
+            ));
+        }
+
         let docs = &self.docs;
 
-        tokens.extend(quote! {
+        quote! {
             #(#[doc = #docs])*
             #[allow(clippy::module_inception)]
             pub mod #ident {
                 #body
             }
-        });
+        }
+    }
+}
+
+impl ToTokens for Peripheral {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.generate(None, &[]));
     }
 }
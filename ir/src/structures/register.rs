@@ -7,8 +7,12 @@ use syn::{Ident, Path, parse_quote};
 
 use crate::{
     access::{Access, ReadWrite},
+    bus::{Bus, Interface},
     structures::field::Numericity,
-    utils::diagnostic::{Context, Diagnostic, Diagnostics},
+    utils::{
+        diagnostic::{Context, Diagnostic, Diagnostics, Location},
+        naming::{self, Namespace},
+    },
 };
 
 use super::{entitlement::Entitlement, field::Field};
@@ -19,10 +23,13 @@ pub struct Register {
     pub offset: u32,
     pub fields: HashMap<Ident, Field>,
     pub reset: Option<u32>,
+    pub shadowed: bool,
     pub docs: Vec<String>,
+    pub location: Location,
 }
 
 impl Register {
+    #[track_caller]
     pub fn new(
         ident: impl AsRef<str>,
         offset: u32,
@@ -35,7 +42,9 @@ impl Register {
                 fields.into_iter().map(|field| (field.module_name(), field)),
             ),
             reset: None,
+            shadowed: false,
             docs: Vec::new(),
+            location: Location::caller(),
         }
     }
 
@@ -45,6 +54,14 @@ impl Register {
         self
     }
 
+    /// Keep the value most recently written to the register in RAM, so write-only registers can be
+    /// modified.
+    pub fn shadowed(mut self) -> Self {
+        self.shadowed = true;
+
+        self
+    }
+
     #[expect(unused)]
     pub fn entitlements(mut self, entitlements: impl IntoIterator<Item = Entitlement>) -> Self {
         todo!()
@@ -70,28 +87,38 @@ impl Register {
         self.fields.values().any(|field| field.is_resolvable())
     }
 
+    /// Validate the register as a memory-mapped register.
     pub fn validate(&self, context: &Context) -> Diagnostics {
+        self.diagnostics(context, None)
+    }
+
+    /// Validate the register, accessed through the bus if specified.
+    ///
+    /// *Note: Device register addresses are not subject to memory-mapped alignment.*
+    pub(crate) fn diagnostics(&self, context: &Context, bus: Option<&Bus>) -> Diagnostics {
         let mut diagnostics = Diagnostics::new();
         let new_context = context.clone().and(self.module_name().to_string());
 
-        if self.offset % 4 != 0 {
+        if bus.is_none() && !self.offset.is_multiple_of(4) {
             diagnostics.insert(
                 Diagnostic::error(format!(
                     "register offset must be word aligned. (offset {} does not satisfy: offset % 4 == 0)",
                     self.offset
                 ))
+                    .with_code("register-misaligned")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
         }
 
         let mut fields = self.fields.values().collect::<Vec<_>>();
-        fields.sort_by(|lhs, rhs| lhs.offset.cmp(&rhs.offset));
+        fields.sort_by_key(|field| field.offset);
 
         for (i, field) in fields.iter().enumerate() {
             let remaining = &fields[i + 1..];
 
             for other in remaining {
-                if field.offset + field.width <= other.offset {
+                if u32::from(field.offset) + u32::from(field.width) <= u32::from(other.offset) {
                     break;
                 }
 
@@ -109,6 +136,8 @@ impl Register {
                         field.module_name().to_string().bold(),
                         other.module_name().to_string().bold()
                     ))
+                    .with_code("field-overlap")
+                    .with_location(other.location.clone())
                     .with_context(new_context.clone())
                     .notes(
                         if !field.entitlements.is_empty() || !other.entitlements.is_empty() {
@@ -125,16 +154,18 @@ impl Register {
             }
         }
 
-        if let Some(field) = fields.last()
-            && field.offset + field.width > 32
-        {
-            diagnostics.insert(
-                Diagnostic::error(format!(
-                    "field [{}] exceeds register width.",
-                    field.module_name().to_string().bold()
-                ))
-                .with_context(new_context.clone()),
-            );
+        for field in &fields {
+            if u32::from(field.offset) + u32::from(field.width) > 32 {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "field [{}] exceeds register width.",
+                        field.module_name().to_string().bold()
+                    ))
+                    .with_code("field-exceeds-register")
+                    .with_location(field.location.clone())
+                    .with_context(new_context.clone()),
+                );
+            }
         }
 
         if self.is_resolvable() && self.reset.is_none() {
@@ -151,10 +182,102 @@ impl Register {
                         .collect::<Vec<_>>()
                         .join(", ")
                 )])
+                .with_code("missing-reset")
+                .with_location(self.location.clone())
                 .with_context(new_context.clone()),
             );
         }
 
+        if self.shadowed {
+            let readable = fields
+                .iter()
+                .filter(|field| field.access.is_read())
+                .map(|field| field.module_name().to_string().bold().to_string())
+                .collect::<Vec<_>>();
+
+            if !readable.is_empty() {
+                diagnostics.insert(
+                    Diagnostic::error("only write-only registers can be shadowed")
+                        .notes([
+                            format!("readable fields: {}", readable.join(", ")),
+                            "readable registers can be modified directly".to_string(),
+                        ])
+                        .with_code("shadow-readable")
+                        .with_location(self.location.clone())
+                        .with_context(new_context.clone()),
+                );
+            }
+
+            if !fields.iter().any(|field| field.access.is_write()) {
+                diagnostics.insert(
+                    Diagnostic::error("shadowed register has no writable fields")
+                        .with_code("shadow-unwritable")
+                        .with_location(self.location.clone())
+                        .with_context(new_context.clone()),
+                );
+            }
+
+            // resolvable registers already require a reset value
+            if self.reset.is_none() && !self.is_resolvable() {
+                diagnostics.insert(
+                    Diagnostic::error("a reset value must be specified for shadowed registers")
+                        .notes(["the shadow is initialized with the reset value"])
+                        .with_code("missing-reset")
+                        .with_location(self.location.clone())
+                        .with_context(new_context.clone()),
+                );
+            }
+        }
+
+        // the reset states of resolvable fields are statically tracked, so they must be representable
+        if let Some(reset) = self.reset {
+            for field in fields.iter().filter(|field| field.entitlements.is_empty()) {
+                let Some(Numericity::Enumerated { variants }) =
+                    field.resolvable().map(|access| &access.numericity)
+                else {
+                    continue;
+                };
+
+                let bits = field.reset_bits(reset);
+
+                if !variants.values().any(|variant| variant.bits == bits) {
+                    diagnostics.insert(
+                        Diagnostic::error(format!(
+                            "reset value of field [{}] ({bits:#x}) does not correspond to any variant",
+                            field.module_name().to_string().bold()
+                        ))
+                        .notes([format!("register reset value is {reset:#010x}")])
+                        .with_code("reset-unmatched")
+                        .with_location(self.location.clone())
+                        .with_context(new_context.clone()),
+                    );
+                }
+            }
+        }
+
+        let mut namespace = Namespace::new("register module").reserve([
+            "Reader",
+            "Writer",
+            "UnsafeReader",
+            "UnsafeWriter",
+            "EmptyWriter",
+            "InertWriter",
+            "Reset",
+            "States",
+            "Fields",
+            "Adopted",
+        ]);
+
+        for field in &fields {
+            namespace.insert("field", &field.ident, &field.location, naming::lower_case);
+            namespace.insert("field", &field.ident, &field.location, naming::pascal_case);
+            namespace.insert("field", &field.ident, &field.location, |ident| {
+                format!("{}Writer", naming::pascal_case(ident))
+            });
+        }
+
+        diagnostics.extend(namespace.validate(&new_context));
+
         for field in fields {
             diagnostics.extend(field.validate(&new_context));
         }
@@ -165,7 +288,12 @@ impl Register {
 
 // codegen
 impl Register {
-    fn generate_fields<'a>(fields: impl Iterator<Item = &'a Field>) -> TokenStream {
+    fn generate_fields<'a>(
+        fields: impl Iterator<Item = &'a Field>,
+        interface: Interface,
+    ) -> TokenStream {
+        let fields = fields.map(|field| field.generate(interface));
+
         quote! {
             #(
                 #fields
@@ -179,6 +307,67 @@ impl Register {
         }
     }
 
+    /// The value most recently written to the register, kept for registers which cannot be read.
+    fn generate_shadow(reset: Option<u32>) -> TokenStream {
+        let reset = reset.unwrap_or(0);
+
+        quote! {
+            static SHADOW: ::core::sync::atomic::AtomicU32 = ::core::sync::atomic::AtomicU32::new(#reset);
+
+            /// View the value most recently written to the register (or the reset value if never written).
+            pub fn shadow() -> u32 {
+                SHADOW.load(::core::sync::atomic::Ordering::Relaxed)
+            }
+
+            /// Restore the shadow to the reset value, as the register is reverted without being written.
+            #[allow(unused)]
+            pub(super) fn reset_shadow() {
+                SHADOW.store(#reset, ::core::sync::atomic::Ordering::Relaxed);
+            }
+        }
+    }
+
+    fn generate_model<'a>(
+        ident: &Ident,
+        fields: impl Iterator<Item = &'a Field>,
+        reset: Option<u32>,
+    ) -> TokenStream {
+        let ident = ident.to_string();
+
+        let reset = match reset {
+            Some(reset) => quote! { Some(#reset) },
+            None => quote! { None },
+        };
+
+        let fields = fields.map(|field| {
+            let ident = field.module_name().to_string();
+            let offset = field.offset;
+            let width = field.width;
+            let readable = field.access.is_read();
+            let writable = field.access.is_write();
+
+            quote! {
+                ::proto_hal::sim::FieldModel {
+                    ident: #ident,
+                    offset: #offset,
+                    width: #width,
+                    readable: #readable,
+                    writable: #writable,
+                }
+            }
+        });
+
+        quote! {
+            #[cfg(feature = "sim")]
+            pub const MODEL: ::proto_hal::sim::RegisterModel = ::proto_hal::sim::RegisterModel {
+                ident: #ident,
+                offset: OFFSET as _,
+                reset: #reset,
+                fields: &[#(#fields,)*],
+            };
+        }
+    }
+
     fn generate_refined_writers<'a>(
         fields: impl Iterator<Item = &'a Field>,
     ) -> Option<TokenStream> {
@@ -197,9 +386,9 @@ impl Register {
             .collect::<Vec<_>>();
 
         // unresolvable numeric fields don't use a refined writer
-        if fields.iter().all(|field| {
-            !field.is_resolvable()
-                && matches!(
+        let is_refined = |field: &Field| {
+            field.is_resolvable()
+                || !matches!(
                     field
                         .access
                         .get_write()
@@ -207,13 +396,19 @@ impl Register {
                         .numericity,
                     Numericity::Numeric
                 )
-        }) {
+        };
+
+        if !fields.iter().any(|field| is_refined(field)) {
             None?
         }
 
         let mut writers = quote! {};
 
         for (i, field) in fields.iter().enumerate() {
+            if !is_refined(field) {
+                continue;
+            }
+
             let field_ident = field.module_name();
             let field_ty = field.type_name();
             let refined_writer_ident = format_ident!("{}Writer", field.type_name());
@@ -405,8 +600,13 @@ impl Register {
     fn generate_unsafe_interface<'a>(
         fields: impl Iterator<Item = &'a Field> + Clone,
         reset: Option<u32>,
+        shadowed: bool,
+        interface: Interface,
     ) -> TokenStream {
-        fn read<'a>(fields: impl Iterator<Item = &'a Field> + Clone) -> Option<TokenStream> {
+        fn read<'a>(
+            fields: impl Iterator<Item = &'a Field> + Clone,
+            interface: Interface,
+        ) -> Option<TokenStream> {
             if fields.clone().any(|field| field.access.is_read()) {
                 let enumerated_field_idents =
                     fields.clone().filter_map(|field| match &field.access {
@@ -437,6 +637,13 @@ impl Register {
                     _ => None,
                 });
 
+                let asyncness = interface.asyncness();
+                let generic = interface.generic();
+                let param = interface.param();
+                let ret = interface.ret(Some(quote! { UnsafeReader }));
+                let value = interface.read(false);
+                let ok = interface.ok(Some(quote! { UnsafeReader { value: #value } }));
+
                 Some(quote! {
                     #[derive(Clone, Copy)]
                     pub struct UnsafeReader {
@@ -474,10 +681,8 @@ impl Register {
                     ///
                     /// Invoking this function will render statically tracked operations unsound if the operation's
                     /// invariances are violated by the effects of the invocation.
-                    pub unsafe fn read_untracked() -> UnsafeReader {
-                        UnsafeReader {
-                            value: unsafe { ::core::ptr::read_volatile((super::base_addr() + OFFSET) as *const u32) }
-                        }
+                    pub #asyncness unsafe fn read_untracked<#generic>(#param) #ret {
+                        #ok
                     }
                 })
             } else {
@@ -485,7 +690,10 @@ impl Register {
             }
         }
 
-        fn write<'a>(fields: impl Iterator<Item = &'a Field> + Clone) -> Option<TokenStream> {
+        fn write<'a>(
+            fields: impl Iterator<Item = &'a Field> + Clone,
+            interface: Interface,
+        ) -> Option<TokenStream> {
             if !fields.clone().any(|field| field.access.is_write()) {
                 None?
             }
@@ -511,6 +719,13 @@ impl Register {
                 )
                 .collect::<Vec<_>>();
 
+            let asyncness = interface.asyncness();
+            let generic = interface.generic();
+            let param = interface.param();
+            let ret = interface.ret(None);
+            let write = interface.write(quote! { writer.value }, false);
+            let ok = interface.ok(None);
+
             Some(quote! {
                 pub struct UnsafeWriter {
                     value: u32
@@ -551,12 +766,13 @@ impl Register {
                 ///
                 /// Invoking this function will render statically tracked operations unsound if the operation's
                 /// invariances are violated by the effects of the invocation.
-                pub unsafe fn write_from_zero_untracked(f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) {
+                pub #asyncness unsafe fn write_from_zero_untracked<#generic>(#param f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) #ret {
                     let mut writer = UnsafeWriter { value: 0 };
 
                     f(&mut writer);
 
-                    unsafe { ::core::ptr::write_volatile((super::base_addr() + OFFSET) as *mut u32, writer.value) };
+                    #write
+                    #ok
                 }
             })
         }
@@ -564,14 +780,25 @@ impl Register {
         fn modify<'a>(
             fields: impl Iterator<Item = &'a Field> + Clone,
             reset: Option<u32>,
+            shadowed: bool,
+            interface: Interface,
         ) -> Option<TokenStream> {
-            if !fields
-                .clone()
-                .any(|field| field.access.is_read() && field.access.is_write())
+            if !shadowed
+                && !fields
+                    .clone()
+                    .any(|field| field.access.is_read() && field.access.is_write())
             {
                 None?
             }
 
+            let asyncness = interface.asyncness();
+            let generic = interface.generic();
+            let param = interface.param();
+            let arg = interface.arg();
+            let complete = interface.complete();
+            let ret = interface.ret(None);
+            let ok = interface.ok(None);
+
             let mut out = quote! {};
 
             if reset.is_some() {
@@ -583,16 +810,52 @@ impl Register {
                     ///
                     /// Invoking this function will render statically tracked operations unsound if the operation's
                     /// invariances are violated by the effects of the invocation.
-                    pub unsafe fn write_from_reset_untracked(f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) {
+                    pub #asyncness unsafe fn write_from_reset_untracked<#generic>(#param f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) #ret {
                         unsafe {
-                            write_from_zero_untracked(|w| {
+                            write_from_zero_untracked(#arg |w| {
                                 f(<ResetWriter as ::proto_hal::stasis::Conjure>::conjure().finish(w))
                             })
-                        }
+                        }#complete;
+                        #ok
+                    }
+                });
+            }
+
+            // exclusive access to the bus already guarantees the read-modify-write is not interrupted
+            let cs = match interface.bus() {
+                Some(..) => param,
+                None => Some(
+                    quote! { #[expect(unused)] cs: ::proto_hal::critical_section::CriticalSection<'_>, },
+                ),
+            };
+            let write = interface.write(quote! { writer.value }, true);
+
+            if shadowed {
+                out.extend(quote! {
+                    /// Modify the contents of the register starting from the shadow value, ignoring implicative
+                    /// effects.
+                    ///
+                    /// # Safety
+                    ///
+                    /// Invoking this function will render statically tracked operations unsound if the operation's
+                    /// invariances are violated by the effects of the invocation.
+                    pub #asyncness unsafe fn modify_untracked<#generic>(#cs f: impl FnOnce(&mut UnsafeWriter) -> &mut UnsafeWriter) #ret {
+                        let mut writer = UnsafeWriter { value: shadow() };
+
+                        f(&mut writer);
+
+                        #write
+                        #ok
                     }
                 });
+
+                return Some(out);
             }
 
+            let read = interface.read(true);
+            let modify_ret = interface.ret(Some(quote! { UnsafeReader }));
+            let modify_ok = interface.ok(Some(quote! { reader }));
+
             out.extend(quote! {
                 /// Read the contents of a register for modification which can be written back, ignoring implicative
                 /// effects.
@@ -601,24 +864,23 @@ impl Register {
                 ///
                 /// Invoking this function will render statically tracked operations unsound if the operation's
                 /// invariances are violated by the effects of the invocation.
-                pub unsafe fn modify_untracked(#[expect(unused)] cs: ::proto_hal::critical_section::CriticalSection<'_>, f: impl FnOnce(UnsafeReader, &mut UnsafeWriter) -> &mut UnsafeWriter) -> UnsafeReader {
-                    let reader = unsafe { read_untracked() };
+                pub #asyncness unsafe fn modify_untracked<#generic>(#cs f: impl FnOnce(UnsafeReader, &mut UnsafeWriter) -> &mut UnsafeWriter) #modify_ret {
+                    let reader = UnsafeReader { value: #read };
                     let mut writer = UnsafeWriter { value: reader.value };
 
                     f(reader, &mut writer);
 
-                    unsafe { ::core::ptr::write_volatile((super::base_addr() + OFFSET) as *mut u32, writer.value) };
-
-                    reader
+                    #write
+                    #modify_ok
                 }
             });
 
             Some(out)
         }
 
-        let read = read(fields.clone());
-        let write = write(fields.clone());
-        let modify = modify(fields, reset);
+        let read = read(fields.clone(), interface);
+        let write = write(fields.clone(), interface);
+        let modify = modify(fields, reset, shadowed, interface);
 
         quote! {
             #read
@@ -629,6 +891,7 @@ impl Register {
 
     fn maybe_generate_reader<'a>(
         fields: impl Iterator<Item = &'a Field> + Clone,
+        interface: Interface,
     ) -> Option<TokenStream> {
         let accessors = fields.filter_map(|field| match &field.access {
             Access::Read(read) | Access::ReadWrite(ReadWrite::Symmetrical(read) | ReadWrite::Asymmetrical { read, .. }) => {
@@ -681,6 +944,14 @@ impl Register {
             None?
         }
 
+        let asyncness = interface.asyncness();
+        let generic = interface.generic();
+        let param = interface.param();
+        let arg = interface.arg();
+        let complete = interface.complete();
+        let ret = interface.ret(Some(quote! { Reader }));
+        let ok = interface.ok(Some(quote! { Reader { r } }));
+
         Some(quote! {
             pub struct Reader {
                 r: UnsafeReader,
@@ -691,8 +962,10 @@ impl Register {
             }
 
             // TODO: track potential effects
-            pub fn read() -> Reader {
-                Reader { r: unsafe { read_untracked() } }
+            pub #asyncness fn read<#generic>(#param) #ret {
+                let r = unsafe { read_untracked(#arg) }#complete;
+
+                #ok
             }
         })
     }
@@ -701,6 +974,8 @@ impl Register {
         fields: impl Iterator<Item = &'a Field> + Clone,
         entitlement_bounds: impl Iterator<Item = &'a TokenStream>,
         reset: Option<u32>,
+        shadowed: bool,
+        interface: Interface,
     ) -> Option<TokenStream> {
         let fields = fields
             .filter(|field| field.access.is_write())
@@ -980,10 +1255,10 @@ impl Register {
             })
             .collect::<Vec<_>>();
 
-        let (states_return, states_conjure) = if fields.iter().any(|field| field.is_resolvable()) {
+        let (states, states_conjure) = if fields.iter().any(|field| field.is_resolvable()) {
             (
                 Some(
-                    quote! { -> States<#(<#resolvable_field_tys as ::proto_hal::stasis::PartialConjure>::Target,)*> },
+                    quote! { States<#(<#resolvable_field_tys as ::proto_hal::stasis::PartialConjure>::Target,)*> },
                 ),
                 Some(
                     quote! { unsafe { <States<#(#resolvable_field_tys,)*> as::proto_hal::stasis::PartialConjure>::partial_conjure() } },
@@ -993,13 +1268,62 @@ impl Register {
             (None, None)
         };
 
-        if fields.iter().any(|field| field.access.is_read()) {
+        let asyncness = interface.asyncness();
+        let generic = interface.generic();
+        let param = interface.param();
+        let arg = interface.arg();
+        let complete = interface.complete();
+        let states_return = interface.ret(states);
+        let states_ok = interface.ok(states_conjure.clone());
+
+        // shadowed registers are modified from the shadow, so there is no reader
+        let (gate, gate_call) = if shadowed {
+            (
+                quote! { impl FnOnce(EmptyWriter) -> Writer<#(#field_tys,)*> },
+                quote! { |w| gate(Writer::empty()).finish(w) },
+            )
+        } else {
+            (
+                quote! { impl FnOnce(Reader, EmptyWriter) -> Writer<#(#field_tys,)*> },
+                quote! { |r, w| gate(Reader { r }, Writer::empty()).finish(w) },
+            )
+        };
+        let modifiable = shadowed || fields.iter().any(|field| field.access.is_read());
+
+        if interface.bus().is_some() && modifiable {
+            out.extend(quote! {
+                /// Modify the contents of the register by passing field handles through the gate.
+                ///
+                /// The exclusive borrow of the bus guarantees the read-modify-write sequence is not interleaved
+                /// with other accesses. If the bus fails, the field states are lost and must be recovered
+                /// with `conjure`.
+                #[allow(clippy::type_complexity)]
+                pub #asyncness fn modify<#generic #(#field_tys,)*>(#param gate: #gate) #states_return
+                where
+                    #(
+                        #field_tys: ::proto_hal::stasis::Emplace<UnsafeWriter> +
+                        ::proto_hal::stasis::Position<#field_idents::Field>,
+                    )*
+                    #(
+                        #resolvable_field_tys: ::proto_hal::stasis::PartialConjure,
+                        #resolvable_field_tys::Target: ::proto_hal::stasis::Position<#resolvable_field_idents::Field>,
+                    )*
+                    #(
+                        #entitlement_bounds,
+                    )*
+                {
+                    unsafe { modify_untracked(#arg #gate_call) }#complete;
+
+                    #states_ok
+                }
+            })
+        } else if modifiable {
             out.extend(quote! {
                 /// Modify the contents of the register by passing field handles through the gate.
                 ///
                 /// This function accepts a critical section to avoid nested critical section acquisition.
                 #[allow(clippy::type_complexity)]
-                pub fn modify_in_cs<#(#field_tys,)*>(cs: ::proto_hal::critical_section::CriticalSection<'_>, gate: impl FnOnce(Reader, EmptyWriter) -> Writer<#(#field_tys,)*>) #states_return
+                pub fn modify_in_cs<#(#field_tys,)*>(cs: ::proto_hal::critical_section::CriticalSection<'_>, gate: #gate) #states_return
                 where
                     #(
                         #field_tys: ::proto_hal::stasis::Emplace<UnsafeWriter> +
@@ -1013,7 +1337,7 @@ impl Register {
                         #entitlement_bounds,
                     )*
                 {
-                    unsafe { modify_untracked(cs, |r, w| gate(Reader { r }, Writer::empty()).finish(w)) };
+                    unsafe { modify_untracked(cs, #gate_call) };
 
                     #states_conjure
                 }
@@ -1023,7 +1347,7 @@ impl Register {
                 /// This function acquires a critical section to perform the read-modify-write sequence.
                 /// Use [`modify_in_cs`] to accept an existing critical section context.
                 #[allow(clippy::type_complexity)]
-                pub fn modify<#(#field_tys,)*>(gate: impl FnOnce(Reader, EmptyWriter) -> Writer<#(#field_tys,)*>) #states_return
+                pub fn modify<#(#field_tys,)*>(gate: #gate) #states_return
                 where
                     #(
                         #field_tys: ::proto_hal::stasis::Emplace<UnsafeWriter> +
@@ -1044,7 +1368,7 @@ impl Register {
 
         out.extend(quote! {
             #[allow(clippy::type_complexity)]
-            pub fn write<#(#field_tys,)*>(gate: impl FnOnce(InertWriter) -> Writer<#(#field_tys,)*>) #states_return
+            pub #asyncness fn write<#generic #(#field_tys,)*>(#param gate: impl FnOnce(InertWriter) -> Writer<#(#field_tys,)*>) #states_return
             where
                 #(
                     #field_tys: ::proto_hal::stasis::Emplace<UnsafeWriter> +
@@ -1059,9 +1383,9 @@ impl Register {
                     #entitlement_bounds,
                 )*
             {
-                unsafe { write_from_zero_untracked(|w| gate(Writer::inert()).finish(w)) };
+                unsafe { write_from_zero_untracked(#arg |w| gate(Writer::inert()).finish(w)) }#complete;
 
-                #states_conjure
+                #states_ok
             }
         });
 
@@ -1076,17 +1400,25 @@ impl Register {
             .clone()
             .map(|field| field.module_name())
             .collect::<Vec<_>>();
+        let states = fields
+            .clone()
+            .map(|field| field.type_name())
+            .collect::<Vec<_>>();
         let reset_tys = fields
             .map(|field| field.reset_ty(reset))
             .collect::<Vec<_>>();
 
         quote! {
-            pub struct Reset {
+            /// The states of all fields of the register.
+            pub struct Fields<#(#states,)*> {
                 #(
-                    pub #field_idents: #field_idents::#reset_tys,
+                    pub #field_idents: #states,
                 )*
             }
 
+            /// The states of all fields of the register in reset.
+            pub type Reset = Fields<#(#field_idents::#reset_tys,)*>;
+
             impl Reset {
                 /// # Safety
                 /// TODO: link to conjure docs.
@@ -1102,6 +1434,117 @@ impl Register {
         }
     }
 
+    /// Reconstruction of the field states from the live register.
+    ///
+    /// *Note: Bus-backed accesses are fallible and require the bus, so no adoption is generated.*
+    fn generate_adopt<'a>(
+        ident: &Ident,
+        fields: impl Iterator<Item = &'a Field> + Clone,
+        interface: Interface,
+    ) -> Option<TokenStream> {
+        if interface.bus().is_some() {
+            None?
+        }
+
+        let register_str = ident.to_string();
+
+        // only enumerated fields can hold values which do not correspond to a variant
+        let checked_fields = fields
+            .clone()
+            .filter(|field| {
+                field
+                    .access
+                    .get_read()
+                    .is_some_and(|read| matches!(read.numericity, Numericity::Enumerated { .. }))
+            })
+            .collect::<Vec<_>>();
+
+        let checks = checked_fields.iter().map(|field| {
+            let field_ident = field.module_name();
+            let field_str = field_ident.to_string();
+            let bits_ident = format_ident!("{}_bits", field_ident);
+            let variant_ident = format_ident!("{}_variant", field_ident);
+
+            quote! {
+                let #bits_ident = (value >> #field_ident::OFFSET) & (u32::MAX >> (32 - #field_ident::WIDTH));
+                let #variant_ident = #field_ident::ReadVariant::try_from_bits(#bits_ident);
+
+                if #variant_ident.is_none() {
+                    report(::proto_hal::adopt::Unmodeled {
+                        peripheral: super::IDENT,
+                        register: #register_str,
+                        field: #field_str,
+                        bits: #bits_ident,
+                    });
+                }
+            }
+        });
+
+        let read = (!checked_fields.is_empty()).then(|| {
+            quote! {
+                let value = unsafe { read_raw(false) };
+            }
+        });
+
+        let report = if checked_fields.is_empty() {
+            quote! { #[expect(unused)] report }
+        } else {
+            quote! { report }
+        };
+
+        let field_idents = fields
+            .clone()
+            .map(|field| field.module_name())
+            .collect::<Vec<_>>();
+        let adopted_tys = fields.clone().map(|field| field.adopted_ty());
+        let adopted_states = fields.map(|field| {
+            let field_ident = field.module_name();
+            let variant_ident = format_ident!("{}_variant", field_ident);
+
+            if !field.entitlements.is_empty() {
+                quote! { unsafe { <#field_ident::Unavailable as ::proto_hal::stasis::Conjure>::conjure() } }
+            } else if field.resolved_variants().is_some() {
+                quote! {
+                    match #variant_ident {
+                        Some(variant) => Ok(unsafe { #field_ident::Resolved::conjure(variant) }),
+                        None => Err(unsafe { <#field_ident::Dynamic as ::proto_hal::stasis::Conjure>::conjure() }),
+                    }
+                }
+            } else {
+                quote! { unsafe { <#field_ident::Dynamic as ::proto_hal::stasis::Conjure>::conjure() } }
+            }
+        });
+
+        Some(quote! {
+            /// The field states reconstructed from the live register.
+            ///
+            /// Resolvable fields holding a value which does not correspond to any variant are
+            /// adopted as dynamic.
+            pub struct Adopted {
+                #(
+                    pub #field_idents: #adopted_tys,
+                )*
+            }
+
+            /// Read the register and reconstruct the states of its fields.
+            ///
+            /// Fields holding values which do not correspond to any variant are reported.
+            ///
+            /// # Safety
+            /// The field states must not exist elsewhere.
+            pub unsafe fn adopt(#report: &mut impl FnMut(::proto_hal::adopt::Unmodeled)) -> Adopted {
+                #read
+                #(#checks)*
+
+                Adopted {
+                    #(
+                        #field_idents: #adopted_states,
+                    )*
+                }
+            }
+        })
+    }
+
     fn generate_states_struct<'a>(
         fields: impl Iterator<Item = &'a Field> + Clone,
     ) -> Option<TokenStream> {
@@ -1151,6 +1594,154 @@ impl Register {
         })
     }
 
+    /// Restoration of the field states to reset by writing the reset value.
+    ///
+    /// *Note: Bus-backed accesses are fallible and require the bus, so no restoration is generated.*
+    fn generate_into_reset<'a>(
+        fields: impl Iterator<Item = &'a Field> + Clone,
+        reset: Option<u32>,
+        shadowed: bool,
+        interface: Interface,
+    ) -> Option<TokenStream> {
+        if interface.bus().is_some() {
+            None?
+        }
+
+        // only registers with a reset value and writable contents can be written to reset
+        let write = reset
+            .filter(|_| fields.clone().any(|field| field.access.is_write()))
+            .map(|reset| {
+                quote! {
+                    unsafe { write_from_zero_untracked(|w| { w.set_bits(#reset); w }) };
+                }
+            });
+
+        let mut out = quote! {
+            impl ::proto_hal::IntoReset for Adopted {
+                type Reset = Reset;
+
+                fn into_reset(self) -> Self::Reset {
+                    #write
+
+                    unsafe { Reset::conjure() }
+                }
+            }
+        };
+
+        let resolvable = fields
+            .clone()
+            .filter(|field| field.is_resolvable())
+            .collect::<Vec<_>>();
+
+        // states of entitled fields are not representable in reset
+        if resolvable
+            .iter()
+            .any(|field| !field.entitlements.is_empty())
+        {
+            out.extend(quote! {
+                impl ::proto_hal::IntoReset for Reset {
+                    type Reset = Reset;
+
+                    fn into_reset(self) -> Self::Reset {
+                        self
+                    }
+                }
+            });
+
+            return Some(out);
+        }
+
+        // holding the states of all fields, the whole register is written
+        let field_idents = fields
+            .clone()
+            .map(|field| field.module_name())
+            .collect::<Vec<_>>();
+        let states = fields
+            .clone()
+            .map(|field| field.type_name())
+            .collect::<Vec<_>>();
+
+        out.extend(quote! {
+            impl<#(#states,)*> ::proto_hal::IntoReset for Fields<#(#states,)*>
+            where
+                #(
+                    #states: ::proto_hal::stasis::Outgoing<#field_idents::Field>,
+                )*
+            {
+                type Reset = Reset;
+
+                fn into_reset(self) -> Self::Reset {
+                    #write
+
+                    unsafe { Reset::conjure() }
+                }
+            }
+        });
+
+        // holding the states of only the resolvable fields, only those fields are written
+        if let Some(reset) = reset
+            && !resolvable.is_empty()
+        {
+            let field_idents = resolvable
+                .iter()
+                .map(|field| field.module_name())
+                .collect::<Vec<_>>();
+            let states = resolvable
+                .iter()
+                .map(|field| field.type_name())
+                .collect::<Vec<_>>();
+            let reset_tys = resolvable
+                .iter()
+                .map(|field| field.reset_ty(Some(reset)))
+                .collect::<Vec<_>>();
+
+            let mask = resolvable.iter().fold(0u32, |mask, field| {
+                mask | u32::MAX
+                    .checked_shr(32u32.saturating_sub(field.width.into()))
+                    .unwrap_or(0)
+                    .checked_shl(field.offset.into())
+                    .unwrap_or(0)
+            });
+            let bits = reset & mask;
+
+            let value = match (mask, bits) {
+                (u32::MAX, ..) => quote! { #bits },
+                (.., 0) => quote! { w.bits() & !#mask },
+                _ => quote! { (w.bits() & !#mask) | #bits },
+            };
+            let modify = if shadowed {
+                quote! { |w| { w.set_bits(#value); w } }
+            } else {
+                quote! { |_, w| { w.set_bits(#value); w } }
+            };
+
+            out.extend(quote! {
+                impl<#(#states,)*> ::proto_hal::IntoReset for States<#(#states,)*>
+                where
+                    #(
+                        #states: ::proto_hal::stasis::Outgoing<#field_idents::Field>,
+                    )*
+                {
+                    type Reset = States<#(#field_idents::#reset_tys,)*>;
+
+                    fn into_reset(self) -> Self::Reset {
+                        ::proto_hal::critical_section::with(|cs| {
+                            unsafe { modify_untracked(cs, #modify) };
+                        });
+
+                        States {
+                            #(
+                                #field_idents: unsafe { <#field_idents::#reset_tys as ::proto_hal::stasis::Conjure>::conjure() },
+                            )*
+                        }
+                    }
+                }
+            });
+        }
+
+        Some(out)
+    }
+
     fn create_entitlement_bounds<'a>(fields: impl Iterator<Item = &'a Field>) -> Vec<TokenStream> {
         fields
             .filter_map(|field| {
@@ -1174,7 +1765,7 @@ impl Register {
                             .flat_map(|variant| {
                                 variant.entitlements.iter().map(|entitlement| {
                                     Ident::new(
-                                        inflector::cases::pascalcase::to_pascal_case(
+                                        naming::pascal_case(
                                             entitlement.field().to_string().as_str(),
                                         )
                                         .as_str(),
@@ -1201,20 +1792,34 @@ impl Register {
     }
 }
 
-impl ToTokens for Register {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+impl Register {
+    /// Generate the register module with the specified access interface.
+    pub(crate) fn generate(&self, interface: Interface) -> TokenStream {
         let mut body = quote! {};
 
         let module_name = self.module_name();
 
-        body.extend(Self::generate_fields(self.fields.values()));
+        body.extend(Self::generate_fields(self.fields.values(), interface));
         body.extend(Self::generate_layout_consts(self.offset));
+        body.extend(interface.raw_access(&module_name.to_string(), self.shadowed));
+        if interface.bus().is_none() {
+            body.extend(Self::generate_model(
+                &module_name,
+                self.fields.values(),
+                self.reset,
+            ));
+        }
+        if self.shadowed {
+            body.extend(Self::generate_shadow(self.reset));
+        }
         body.extend(Self::generate_unsafe_interface(
             self.fields.values(),
             self.reset,
+            self.shadowed,
+            interface,
         ));
         body.extend(Self::generate_refined_writers(self.fields.values()));
-        body.extend(Self::maybe_generate_reader(self.fields.values()));
+        body.extend(Self::maybe_generate_reader(self.fields.values(), interface));
 
         let entitlement_bounds = Self::create_entitlement_bounds(self.fields.values());
 
@@ -1222,16 +1827,35 @@ impl ToTokens for Register {
             self.fields.values(),
             entitlement_bounds.iter(),
             self.reset,
+            self.shadowed,
+            interface,
         ));
         body.extend(Self::generate_reset(self.fields.values(), self.reset));
         body.extend(Self::generate_states_struct(self.fields.values()));
+        body.extend(Self::generate_adopt(
+            &module_name,
+            self.fields.values(),
+            interface,
+        ));
+        body.extend(Self::generate_into_reset(
+            self.fields.values(),
+            self.reset,
+            self.shadowed,
+            interface,
+        ));
 
         let docs = &self.docs;
-        tokens.extend(quote! {
+        quote! {
             #(#[doc = #docs])*
             pub mod #module_name {
                 #body
             }
-        });
+        }
+    }
+}
+
+impl ToTokens for Register {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        tokens.extend(self.generate(Interface::new(None)));
     }
 }
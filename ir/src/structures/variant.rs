@@ -4,21 +4,39 @@ use syn::Ident;
 
 use crate::{
     structures::entitlement::Entitlements,
-    utils::diagnostic::{Context, Diagnostic, Diagnostics},
+    utils::{
+        diagnostic::{Context, Diagnostic, Diagnostics, Location},
+        naming,
+    },
 };
 
 use super::entitlement::Entitlement;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Variant {
     pub ident: Ident,
     pub bits: u32,
     pub inert: bool,
     pub entitlements: Entitlements,
     pub docs: Vec<String>,
+    pub location: Location,
 }
 
+// note: the definition location does not participate in equality
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        self.ident == other.ident
+            && self.bits == other.bits
+            && self.inert == other.inert
+            && self.entitlements == other.entitlements
+            && self.docs == other.docs
+    }
+}
+
+impl Eq for Variant {}
+
 impl Variant {
+    #[track_caller]
     pub fn new(ident: impl AsRef<str>, bits: u32) -> Self {
         Self {
             ident: Ident::new(ident.as_ref(), Span::call_site()),
@@ -26,6 +44,7 @@ impl Variant {
             inert: false,
             entitlements: Entitlements::new(),
             docs: Vec::new(),
+            location: Location::caller(),
         }
     }
 
@@ -54,14 +73,14 @@ impl Variant {
 
     pub fn module_name(&self) -> Ident {
         Ident::new(
-            inflector::cases::snakecase::to_snake_case(self.ident.to_string().as_str()).as_str(),
+            naming::snake_case(self.ident.to_string().as_str()).as_str(),
             Span::call_site(),
         )
     }
 
     pub fn type_name(&self) -> Ident {
         Ident::new(
-            inflector::cases::pascalcase::to_pascal_case(self.ident.to_string().as_str()).as_str(),
+            naming::pascal_case(self.ident.to_string().as_str()).as_str(),
             Span::call_site(),
         )
     }
@@ -76,6 +95,8 @@ impl Variant {
             diagnostics.insert(
                 Diagnostic::error(format!("\"{}\" is a reserved keyword", self.module_name()))
                     .notes([format!("reserved variant keywords are: {reserved:?}")])
+                    .with_code("reserved-variant-ident")
+                    .with_location(self.location.clone())
                     .with_context(new_context.clone()),
             );
         }
@@ -135,10 +156,7 @@ impl Variant {
 
 impl ToTokens for Variant {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let ident = Ident::new(
-            &inflector::cases::pascalcase::to_pascal_case(self.ident.to_string().as_str()),
-            Span::call_site(),
-        );
+        let ident = self.type_name();
 
         tokens.extend(Self::generate_state(&ident, self.docs.iter()));
         tokens.extend(Self::generate_entitlement_impls(&ident, &self.entitlements));
@@ -4,16 +4,19 @@ use std::{
 };
 
 use colored::Colorize;
+use serde::Serialize;
 use ters::ters;
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Kind {
     Warning,
     Error,
 }
 
 #[ters]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
 pub struct Context {
     #[get]
     path: Vec<String>,
@@ -49,42 +52,97 @@ impl Display for Context {
     }
 }
 
+/// The location within a model crate at which an IR structure was defined.
 #[ters]
-#[derive(Debug, Clone, Hash, PartialEq, Eq)]
-pub struct Diagnostic {
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Location {
     #[get]
-    message: String,
-    notes: Vec<String>,
+    file: String,
+    #[get]
+    line: u32,
+    #[get]
+    column: u32,
+}
+
+impl Location {
+    /// Capture the location of the caller.
+    ///
+    /// Builders which record this location are marked `#[track_caller]` so the location
+    /// points at the model definition rather than into this crate.
+    #[track_caller]
+    pub fn caller() -> Self {
+        let location = std::panic::Location::caller();
+
+        Self {
+            file: location.file().to_string(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+#[ters]
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
     #[get]
     kind: Kind,
     #[get]
+    code: Option<String>,
+    #[get]
     context: Option<Context>,
+    #[get]
+    location: Option<Location>,
+    #[get]
+    message: String,
+    notes: Vec<String>,
 }
 
 impl Diagnostic {
     pub fn warning(message: impl Into<String>) -> Self {
         Self {
-            message: message.into(),
-            notes: Vec::new(),
             kind: Kind::Warning,
+            code: None,
             context: None,
+            location: None,
+            message: message.into(),
+            notes: Vec::new(),
         }
     }
 
     pub fn error(message: impl Into<String>) -> Self {
         Self {
-            message: message.into(),
-            notes: Vec::new(),
             kind: Kind::Error,
+            code: None,
             context: None,
+            location: None,
+            message: message.into(),
+            notes: Vec::new(),
         }
     }
 
+    /// Attach a stable, machine-readable code identifying the kind of problem.
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
     pub fn with_context(mut self, context: Context) -> Self {
         self.context = Some(context);
         self
     }
 
+    /// Attach the location of the model definition responsible for the diagnostic.
+    pub fn with_location(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
     pub fn notes<I>(mut self, notes: I) -> Self
     where
         I: IntoIterator,
@@ -96,6 +154,54 @@ impl Diagnostic {
         self
     }
 
+    /// Order diagnostics by context, then kind, then message, so output is stable between runs.
+    pub fn sorted(diagnostics: &Diagnostics) -> Vec<&Self> {
+        let mut sorted = diagnostics.iter().collect::<Vec<_>>();
+
+        sorted.sort_by(|lhs, rhs| {
+            let key = |diagnostic: &Self| {
+                (
+                    diagnostic
+                        .context
+                        .as_ref()
+                        .map(|context| context.path.clone()),
+                    matches!(diagnostic.kind, Kind::Warning),
+                    diagnostic.message.clone(),
+                )
+            };
+
+            key(lhs).cmp(&key(rhs))
+        });
+
+        sorted
+    }
+
+    /// Render the diagnostic on a single line, as required by line-oriented consumers such as
+    /// cargo build script directives.
+    pub fn oneline(&self) -> String {
+        let mut line = String::new();
+
+        if let Some(location) = &self.location {
+            line.push_str(format!("{location}: ").as_str());
+        }
+
+        if let Some(code) = &self.code {
+            line.push_str(format!("[{code}] ").as_str());
+        }
+
+        if let Some(context) = &self.context {
+            line.push_str(format!("in {}: ", context.path.join("/")).as_str());
+        }
+
+        line.push_str(self.message.as_str());
+
+        for note in &self.notes {
+            line.push_str(format!(" (note: {note})").as_str());
+        }
+
+        line
+    }
+
     pub fn report(diagnostics: &HashSet<Self>) -> String {
         let mut diagnostic_groups = HashMap::new();
 
@@ -124,6 +230,15 @@ impl Diagnostic {
             .collect::<Vec<_>>()
             .join("\n\n")
     }
+
+    /// Render the diagnostics as a JSON array for consumption by external tools.
+    ///
+    /// Each entry contains the `kind`, `code`, `context` path, `location`, `message`, and
+    /// `notes` of a diagnostic.
+    pub fn report_json(diagnostics: &Diagnostics) -> String {
+        serde_json::to_string_pretty(&Self::sorted(diagnostics))
+            .expect("diagnostics are composed of infallibly serializable types")
+    }
 }
 
 impl Display for Diagnostic {
@@ -144,8 +259,17 @@ impl Display for Diagnostic {
             Kind::Warning => "warning".yellow().bold(),
             Kind::Error => "error".red().bold(),
         };
+        let code = match &self.code {
+            Some(code) => format!("[{code}]").bold().to_string(),
+            None => "".to_string(),
+        };
+
+        let location = match &self.location {
+            Some(location) => format!("\n  {} {location}", "-->".bright_blue().bold()),
+            None => "".to_string(),
+        };
 
-        write!(f, "{kind}: {}{notes}", self.message)
+        write!(f, "{kind}{code}: {}{location}{notes}", self.message)
     }
 }
 
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use colored::Colorize;
+use syn::Ident;
+
+use crate::utils::diagnostic::{Context, Diagnostic, Diagnostics, Location};
+
+/// The name transformations applied to model identifiers during codegen.
+///
+/// Structures derive their generated identifiers through these so validation can
+/// simulate codegen exactly.
+pub fn lower_case(ident: &str) -> String {
+    ident.to_lowercase()
+}
+
+pub fn snake_case(ident: &str) -> String {
+    inflector::cases::snakecase::to_snake_case(ident)
+}
+
+pub fn pascal_case(ident: &str) -> String {
+    inflector::cases::pascalcase::to_pascal_case(ident)
+}
+
+pub fn screaming_snake_case(ident: &str) -> String {
+    inflector::cases::screamingsnakecase::to_screaming_snake_case(ident)
+}
+
+/// Whether the name is lexically an identifier (ignoring keywords).
+fn is_ident_like(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Whether the name can be used as a Rust identifier.
+pub fn is_valid_ident(name: &str) -> bool {
+    syn::parse_str::<Ident>(name).is_ok()
+}
+
+struct Entry {
+    kind: &'static str,
+    source: String,
+    generated: String,
+    location: Location,
+    transform: fn(&str) -> String,
+}
+
+/// A set of identifiers generated into the same Rust namespace.
+///
+/// Identifiers which are invalid, collide with each other, or collide with
+/// items generated unconditionally are reported.
+pub(crate) struct Namespace {
+    description: &'static str,
+    reserved: Vec<String>,
+    entries: Vec<Entry>,
+}
+
+impl Namespace {
+    pub fn new(description: &'static str) -> Self {
+        Self {
+            description,
+            reserved: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Reserve names which are always generated within the namespace.
+    pub fn reserve<I>(mut self, names: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        self.reserved
+            .extend(names.into_iter().map(|name| name.as_ref().to_string()));
+        self
+    }
+
+    /// Add the identifier generated from `ident` (by `transform`) to the namespace.
+    pub fn insert(
+        &mut self,
+        kind: &'static str,
+        ident: &Ident,
+        location: &Location,
+        transform: fn(&str) -> String,
+    ) {
+        let source = ident.to_string();
+
+        self.entries.push(Entry {
+            kind,
+            generated: transform(&source),
+            source,
+            location: location.clone(),
+            transform,
+        });
+    }
+
+    fn is_taken(&self, name: &str) -> bool {
+        self.reserved.iter().any(|reserved| reserved == name)
+            || self.entries.iter().any(|entry| entry.generated == name)
+    }
+
+    /// Find an alternative source identifier which produces a valid and unique name.
+    fn suggest(&self, entry: &Entry) -> Option<String> {
+        std::iter::once(format!("{}_", entry.source))
+            .chain((1..10).map(|n| format!("{}_{n}", entry.source)))
+            .find(|candidate| {
+                let generated = (entry.transform)(candidate);
+
+                is_valid_ident(&generated) && !self.is_taken(&generated)
+            })
+    }
+
+    fn suggestion(&self, entry: &Entry) -> Vec<String> {
+        self.suggest(entry)
+            .map(|suggestion| format!("consider renaming [{}] to [{suggestion}]", entry.source))
+            .into_iter()
+            .collect()
+    }
+
+    pub fn validate(&self, context: &Context) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
+
+        let mut groups = HashMap::<&str, Vec<&Entry>>::new();
+
+        for entry in &self.entries {
+            groups.entry(&entry.generated).or_default().push(entry);
+
+            if !is_valid_ident(&entry.generated) {
+                let reason = if is_ident_like(&entry.generated) {
+                    "is a reserved Rust keyword"
+                } else {
+                    "is not a valid identifier"
+                };
+
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "{} [{}] produces `{}` which {reason}",
+                        entry.kind,
+                        entry.source.bold(),
+                        entry.generated
+                    ))
+                    .notes(self.suggestion(entry))
+                    .with_code("invalid-ident")
+                    .with_location(entry.location.clone())
+                    .with_context(context.clone()),
+                );
+            } else if self.reserved.contains(&entry.generated) {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "{} [{}] produces `{}` which collides with a generated item",
+                        entry.kind,
+                        entry.source.bold(),
+                        entry.generated
+                    ))
+                    .notes(
+                        [format!(
+                            "`{}` is generated in every {}",
+                            entry.generated, self.description
+                        )]
+                        .into_iter()
+                        .chain(self.suggestion(entry)),
+                    )
+                    .with_code("ident-collision")
+                    .with_location(entry.location.clone())
+                    .with_context(context.clone()),
+                );
+            }
+        }
+
+        for (generated, mut entries) in groups {
+            entries.sort_by(|lhs, rhs| lhs.source.cmp(&rhs.source));
+
+            let Some((first, rest)) = entries.split_first() else {
+                continue;
+            };
+
+            // a structure may produce the same name through multiple transformations
+            for entry in rest.iter().filter(|entry| entry.source != first.source) {
+                diagnostics.insert(
+                    Diagnostic::error(format!(
+                        "{} [{}] and {} [{}] both produce `{generated}`",
+                        first.kind,
+                        first.source.bold(),
+                        entry.kind,
+                        entry.source.bold()
+                    ))
+                    .notes(
+                        [format!(
+                            "[{}] is defined at {}",
+                            first.source, first.location
+                        )]
+                        .into_iter()
+                        .chain(self.suggestion(entry)),
+                    )
+                    .with_code("ident-collision")
+                    .with_location(entry.location.clone())
+                    .with_context(context.clone()),
+                );
+            }
+        }
+
+        diagnostics
+    }
+}
@@ -1,18 +1,123 @@
-use std::{env, fs, path::Path};
+use std::{collections::HashMap, env, fs, path::Path};
 
 use colored::Colorize as _;
 use ir::{
-    structures::{hal::Hal, interrupts::InterruptKind},
+    lint::{Lint, Lints},
+    structures::{
+        Validated,
+        hal::Hal,
+        interrupts::{InterruptKind, Runtime},
+    },
     utils::diagnostic::{self, Diagnostic, Diagnostics},
 };
 
+/// Configuration of HAL validation and generation.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    lints: Lints,
+    runtime: Option<Runtime>,
+    reset_on_startup: bool,
+    interrupt_map: Option<HashMap<String, usize>>,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable lints which are checked after the model is validated.
+    ///
+    /// *Note: Lints are advisory, so they never fail generation.*
+    pub fn lints(mut self, lints: impl IntoIterator<Item = Lint>) -> Self {
+        self.lints.extend(lints);
+        self
+    }
+
+    /// Select the runtime which the interrupt vector table is generated for, overriding
+    /// the runtime selected by the model (if any).
+    pub fn runtime(mut self, runtime: Runtime) -> Self {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    /// Declare that the peripherals are in the reset state when the application starts, such that
+    /// `Peripherals::take` is available without declaring so at runtime.
+    ///
+    /// *Note: Only declare this for HALs whose applications are always started from reset (i.e.
+    /// no bootloader hands over modified peripherals).*
+    pub fn reset_on_startup(mut self) -> Self {
+        self.reset_on_startup = true;
+        self
+    }
+
+    /// Verify the interrupt table against the expected interrupt numbers (e.g. imported from
+    /// a vendor SVD with [`svd::interrupts`](crate::svd::interrupts)).
+    ///
+    /// *Note: Expected interrupts missing from the table are advisory, as the model may reserve
+    /// them.*
+    pub fn interrupt_map(mut self, expected: HashMap<String, usize>) -> Self {
+        self.interrupt_map = Some(expected);
+        self
+    }
+}
+
+/// Validate the HAL and separate the outcome into the validated HAL (if validation succeeded),
+/// the diagnostics emitted, and the advisory diagnostics emitted (such as lints and missing
+/// interrupts) which never fail generation.
+fn check(mut hal: Hal, config: &Config) -> (Option<Validated<Hal>>, Diagnostics, Diagnostics) {
+    if let Some(runtime) = config.runtime {
+        hal = hal.runtime(runtime);
+    }
+
+    if config.reset_on_startup {
+        hal = hal.reset_on_startup();
+    }
+
+    let (mismatches, missing): (Diagnostics, Diagnostics) = config
+        .interrupt_map
+        .as_ref()
+        .map(|expected| hal.interrupts.verify(expected))
+        .unwrap_or_default()
+        .into_iter()
+        .partition(|diagnostic| matches!(diagnostic.kind(), diagnostic::Kind::Error));
+
+    match hal.validate() {
+        Ok(hal) => {
+            let mut diagnostics = hal.warnings().clone();
+            diagnostics.extend(mismatches.clone());
+
+            let mut advisories = hal.lint(&config.lints);
+            advisories.extend(missing);
+
+            (
+                mismatches.is_empty().then_some(hal),
+                diagnostics,
+                advisories,
+            )
+        }
+        Err(mut diagnostics) => {
+            diagnostics.extend(mismatches);
+
+            (None, diagnostics, missing)
+        }
+    }
+}
+
 /// Validate a HAL model is properly defined and codegen succeeds.
 ///
 /// *Note: This function is intended to be called in the "model" phase of synthesis.*
-pub fn validate(source: impl FnOnce() -> (Hal, Diagnostics)) {
+pub fn validate(source: impl FnOnce() -> Hal) {
+    validate_with(source, Config::new());
+}
+
+/// Validate a HAL model with the provided configuration.
+///
+/// See [`validate`].
+pub fn validate_with(source: impl FnOnce() -> Hal, config: Config) {
     // model validation
     println!("Validating model...");
-    let (hal, diagnostics) = source();
+    let (hal, mut diagnostics, advisories) = check(source(), &config);
+    diagnostics.extend(advisories);
 
     if !diagnostics.is_empty() {
         println!("{}", Diagnostic::report(&diagnostics));
@@ -33,9 +138,9 @@ pub fn validate(source: impl FnOnce() -> (Hal, Diagnostics)) {
     }
     println!("emitted {warning_count} warnings and {error_count} errors");
 
-    if error_count != 0 {
+    let Some(hal) = hal else {
         return;
-    }
+    };
 
     // codegen validation
     println!("Validating codegen...");
@@ -80,12 +185,59 @@ pub fn validate(source: impl FnOnce() -> (Hal, Diagnostics)) {
 
 /// Generate and emit HAL code for use.
 ///
+/// Each diagnostic is emitted as its own `cargo::warning` or `cargo::error` directive so
+/// problems with the model surface directly in the downstream build log. The complete set
+/// of diagnostics is also written to `diagnostics.json` in `OUT_DIR`. Generation fails if any
+/// errors or warnings are emitted, except for advisory warnings such as lints.
+///
+/// The linker scripts `device.x` (default interrupt handlers) and `memory.x` (when the model
+/// describes its memory map, see [`Hal::memory`]) are written to `OUT_DIR`, which is added to
+/// the linker search path.
+///
+/// The emitted code is gated on the following features of the HAL crate:
+/// - `interrupts`: the interrupt vector table.
+/// - `sim`: the register simulator (which should enable `proto-hal/sim`).
+/// - `trace`: register access tracing (which should enable `proto-hal/trace`).
+/// - `test-support`: mock peripheral storage for host tests (enable it from the dev-dependencies
+///   of crates whose tests drive the HAL).
+///
+/// HALs modeling a bus-backed device (see [`Hal::bus`]) are not memory-mapped and have no vector
+/// table, so none of these features apply.
+///
 /// *Note: This function is intended to be called in the "out" phase of synthesis.*
-pub fn generate(source: impl FnOnce() -> (Hal, Diagnostics)) {
+pub fn generate(source: impl FnOnce() -> Hal) {
+    generate_with(source, Config::new());
+}
+
+/// Generate and emit HAL code with the provided configuration.
+///
+/// See [`generate`].
+pub fn generate_with(source: impl FnOnce() -> Hal, config: Config) {
     let out_dir = env::var("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("hal.rs");
 
-    let (hal, diagnostics) = source();
+    // cargo directives are line-oriented plain text, so no styling may be embedded
+    colored::control::set_override(false);
+
+    let (hal, diagnostics, advisories) = check(source(), &config);
+
+    let mut reported = diagnostics.clone();
+    reported.extend(advisories);
+
+    fs::write(
+        Path::new(&out_dir).join("diagnostics.json"),
+        Diagnostic::report_json(&reported),
+    )
+    .unwrap();
+
+    for diagnostic in Diagnostic::sorted(&reported) {
+        let directive = match diagnostic.kind() {
+            diagnostic::Kind::Warning => "warning",
+            diagnostic::Kind::Error => "error",
+        };
+
+        println!("cargo::{directive}={}", diagnostic.oneline());
+    }
 
     let warning_count = diagnostics
         .iter()
@@ -97,31 +249,41 @@ pub fn generate(source: impl FnOnce() -> (Hal, Diagnostics)) {
         .filter(|diagnostic| matches!(diagnostic.kind(), diagnostic::Kind::Error))
         .count();
 
-    match (warning_count, error_count) {
-        (_, 1..) => {
-            println!("cargo::error=HAL generation failed. Refer to the model crate for details.");
+    let hal = match (hal, warning_count, error_count) {
+        (None, ..) | (_, _, 1..) => {
+            println!("cargo::error=HAL generation failed with {error_count} errors.");
             return;
         }
-        (1.., _) => {
-            println!(
-                "cargo::error=HAL generation contains warnings. Refer to the model crate for details."
-            );
+        (_, 1.., _) => {
+            println!("cargo::error=HAL generation contains {warning_count} warnings.");
             return;
         }
-        (..) => {}
-    }
+        (Some(hal), ..) => hal,
+    };
 
-    let Ok(codegen) = hal.render() else {
-        println!("cargo::error=Codegen failed. Refer to the model crate for details.");
-        return;
+    let codegen = match hal.render() {
+        Ok(codegen) => codegen,
+        Err(e) => {
+            println!("cargo::error=Codegen failed: {}", e.replace('\n', " "));
+            return;
+        }
     };
 
     fs::write(&dest_path, codegen).unwrap();
 
     // device.x
-    let dest_path = Path::new(&out_dir).join("device.x");
+    if let Some(device_x) = hal.device_x() {
+        let dest_path = Path::new(&out_dir).join("device.x");
 
-    fs::write(&dest_path, hal.interrupts.device_x()).unwrap();
+        fs::write(&dest_path, device_x).unwrap();
+    }
+
+    // memory.x
+    if let Some(memory_x) = hal.memory_x() {
+        let dest_path = Path::new(&out_dir).join("memory.x");
+
+        fs::write(&dest_path, memory_x).unwrap();
+    }
 
     println!("cargo:rustc-link-search={out_dir}");
 }
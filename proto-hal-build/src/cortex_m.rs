@@ -0,0 +1,86 @@
+//! Prebuilt models of the Cortex-M core peripherals.
+//!
+//! Chip models describe only the vendor peripherals, and merge the architectural peripherals
+//! of the declared core into the HAL:
+//!
+//! ```ignore
+//! let hal = Hal::new([rcc::generate()])
+//!     .interrupts([Interrupt::handler("WWDG")])
+//!     .cpu(Cpu::new(Core::M4, 4).fpu().mpu());
+//!
+//! let hal = proto_hal_build::cortex_m::merge(hal);
+//! ```
+
+pub mod dcb;
+pub mod dwt;
+pub mod fpu;
+pub mod mpu;
+pub mod nvic;
+pub mod scb;
+pub mod sys_tick;
+
+use ir::{
+    access::Access,
+    structures::{
+        cpu::{Architecture, Cpu},
+        field::{Field, Numericity},
+        hal::Hal,
+        peripheral::Peripheral,
+        variant::Variant,
+    },
+};
+
+/// The core peripherals implemented by the `cpu`, with the NVIC sized for `interrupts`
+/// external interrupts.
+pub fn peripherals(cpu: &Cpu, interrupts: usize) -> Vec<Peripheral> {
+    let architecture = cpu.core.architecture();
+
+    let mut peripherals = vec![
+        nvic::generate(architecture, interrupts),
+        scb::generate(architecture, cpu.fpu),
+        sys_tick::generate(),
+    ];
+
+    if !matches!(architecture, Architecture::V6M) {
+        peripherals.extend([dcb::generate(), dwt::generate()]);
+    }
+
+    if cpu.mpu {
+        peripherals.push(mpu::generate(architecture));
+    }
+
+    if cpu.fpu {
+        peripherals.push(fpu::generate());
+    }
+
+    peripherals
+}
+
+/// Merge the core peripherals of the core declared by the HAL into the HAL.
+///
+/// # Panics
+///
+/// Panics if the HAL does not declare its core with [`Hal::cpu`].
+pub fn merge(hal: Hal) -> Hal {
+    let cpu = hal
+        .cpu
+        .as_ref()
+        .expect("the core must be declared to merge the core peripherals");
+
+    let peripherals = peripherals(cpu, hal.interrupts.len());
+
+    hal.peripherals(peripherals)
+}
+
+/// A single bit field which can be disabled or enabled by software.
+fn switch(ident: &str, offset: u8) -> Field {
+    Field::new(
+        ident,
+        offset,
+        1,
+        Access::read_write(Numericity::enumerated([
+            Variant::new("Disabled", 0),
+            Variant::new("Enabled", 1),
+        ])),
+    )
+}
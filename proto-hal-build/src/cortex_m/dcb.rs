@@ -0,0 +1,13 @@
+use ir::structures::{peripheral::Peripheral, register::Register};
+
+use super::switch;
+
+/// The debug control block.
+pub fn generate() -> Peripheral {
+    Peripheral::new(
+        "dcb",
+        0xe000_edf0,
+        [Register::new("demcr", 0xc, [switch("trcena", 24)]).reset(0)],
+    )
+    .docs(["Debug control block."])
+}
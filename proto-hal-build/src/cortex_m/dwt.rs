@@ -0,0 +1,41 @@
+use ir::{
+    access::{Access, HardwareAccess},
+    structures::{
+        entitlement::Entitlement,
+        field::{Field, Numericity},
+        peripheral::Peripheral,
+        register::Register,
+    },
+};
+
+use super::switch;
+
+/// The data watchpoint and trace unit, which is only accessible while trace is enabled.
+pub fn generate() -> Peripheral {
+    Peripheral::new(
+        "dwt",
+        0xe000_1000,
+        [
+            Register::new(
+                "ctrl",
+                0x0,
+                [
+                    switch("cyccntena", 0),
+                    Field::new("numcomp", 28, 4, Access::read(Numericity::Numeric)),
+                ],
+            )
+            .reset(0),
+            Register::new(
+                "cyccnt",
+                0x4,
+                [
+                    Field::new("cyccnt", 0, 32, Access::read_write(Numericity::Numeric))
+                        .hardware_access(HardwareAccess::Write),
+                ],
+            ),
+        ],
+    )
+    .clock_enable(Entitlement::to("dcb::demcr::trcena::Enabled"))
+    .clock_gate(Entitlement::to("dcb::demcr::trcena::Disabled"))
+    .docs(["Data watchpoint and trace unit."])
+}
@@ -0,0 +1,61 @@
+use ir::{
+    access::{Access, HardwareAccess},
+    structures::{
+        field::{Field, Numericity},
+        peripheral::Peripheral,
+        register::Register,
+        variant::Variant,
+    },
+};
+
+use super::switch;
+
+/// The floating point unit context control registers.
+pub fn generate() -> Peripheral {
+    Peripheral::new(
+        "fpu",
+        0xe000_ef34,
+        [
+            Register::new(
+                "fpccr",
+                0x0,
+                [
+                    Field::new("lspact", 0, 1, Access::read(Numericity::Numeric)),
+                    switch("lspen", 30),
+                    switch("aspen", 31),
+                ],
+            )
+            .reset(0xc000_0000),
+            Register::new(
+                "fpcar",
+                0x4,
+                [
+                    Field::new("address", 3, 29, Access::read_write(Numericity::Numeric))
+                        .hardware_access(HardwareAccess::Write),
+                ],
+            ),
+            Register::new(
+                "fpdscr",
+                0x8,
+                [
+                    Field::new(
+                        "rmode",
+                        22,
+                        2,
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new("Nearest", 0),
+                            Variant::new("PlusInfinity", 1),
+                            Variant::new("MinusInfinity", 2),
+                            Variant::new("Zero", 3),
+                        ])),
+                    ),
+                    switch("fz", 24),
+                    switch("dn", 25),
+                    switch("ahp", 26),
+                ],
+            )
+            .reset(0),
+        ],
+    )
+    .docs(["Floating point unit."])
+}
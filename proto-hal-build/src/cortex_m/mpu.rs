@@ -0,0 +1,108 @@
+use ir::{
+    access::{Access, HardwareAccess},
+    structures::{
+        cpu::Architecture,
+        field::{Field, Numericity},
+        peripheral::Peripheral,
+        register::Register,
+    },
+};
+
+use super::switch;
+
+/// The memory protection unit.
+///
+/// *Note: The region registers are laid out as base/attribute pairs prior to ARMv8-M and as
+/// base/limit pairs from ARMv8-M. They are banked by the selected region (which writes to
+/// `rbar` may also select), so they are not statically tracked.*
+pub fn generate(architecture: Architecture) -> Peripheral {
+    let numeric = |ident, offset, width| {
+        Field::new(
+            ident,
+            offset,
+            width,
+            Access::read_write(Numericity::Numeric),
+        )
+        .hardware_access(HardwareAccess::Write)
+    };
+
+    let (rbar, region) = match architecture {
+        Architecture::V6M | Architecture::V7M => (
+            Register::new(
+                "rbar",
+                0xc,
+                [
+                    numeric("region", 0, 4),
+                    numeric("valid", 4, 1),
+                    numeric("addr", 5, 27),
+                ],
+            ),
+            Register::new(
+                "rasr",
+                0x10,
+                [
+                    switch("enable", 0).hardware_access(HardwareAccess::Write),
+                    numeric("size", 1, 5),
+                    numeric("srd", 8, 8),
+                    numeric("b", 16, 1),
+                    numeric("c", 17, 1),
+                    numeric("s", 18, 1),
+                    numeric("tex", 19, 3),
+                    numeric("ap", 24, 3),
+                    numeric("xn", 28, 1),
+                ],
+            ),
+        ),
+        Architecture::V8M => (
+            Register::new(
+                "rbar",
+                0xc,
+                [
+                    numeric("xn", 0, 1),
+                    numeric("ap", 1, 2),
+                    numeric("sh", 3, 2),
+                    numeric("base", 5, 27),
+                ],
+            ),
+            Register::new(
+                "rlar",
+                0x10,
+                [
+                    switch("en", 0).hardware_access(HardwareAccess::Write),
+                    numeric("attrindx", 1, 3),
+                    numeric("limit", 5, 27),
+                ],
+            ),
+        ),
+    };
+
+    Peripheral::new(
+        "mpu",
+        0xe000_ed90,
+        [
+            Register::new(
+                "typer",
+                0x0,
+                [
+                    Field::new("separate", 0, 1, Access::read(Numericity::Numeric)),
+                    Field::new("dregion", 8, 8, Access::read(Numericity::Numeric)),
+                    Field::new("iregion", 16, 8, Access::read(Numericity::Numeric)),
+                ],
+            ),
+            Register::new(
+                "ctrl",
+                0x4,
+                [
+                    switch("enable", 0),
+                    switch("hfnmiena", 1),
+                    switch("privdefena", 2),
+                ],
+            )
+            .reset(0),
+            Register::new("rnr", 0x8, [numeric("region", 0, 8)]),
+            rbar,
+            region,
+        ],
+    )
+    .docs(["Memory protection unit."])
+}
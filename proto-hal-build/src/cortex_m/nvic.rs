@@ -0,0 +1,111 @@
+use ir::{
+    access::{Access, HardwareAccess},
+    structures::{
+        cpu::Architecture,
+        field::{Field, Numericity},
+        peripheral::Peripheral,
+        register::Register,
+        variant::Variant,
+    },
+};
+
+/// The nested vectored interrupt controller, with registers sized for `interrupts` external
+/// interrupts.
+///
+/// *Note: The set/clear-enable registers are written by the generated interrupt masks, so their
+/// states are not tracked.*
+pub fn generate(architecture: Architecture, interrupts: usize) -> Peripheral {
+    let enabled = || [Variant::new("Disabled", 0), Variant::new("Enabled", 1)];
+    let pending = || [Variant::new("Idle", 0), Variant::new("Pending", 1)];
+
+    let mut registers = Vec::new();
+
+    for (ident, offset, field, read, write) in [
+        (
+            "iser",
+            0x000,
+            "setena",
+            enabled(),
+            Variant::new("Enable", 1),
+        ),
+        (
+            "icer",
+            0x080,
+            "clrena",
+            enabled(),
+            Variant::new("Disable", 1),
+        ),
+        ("ispr", 0x100, "setpend", pending(), Variant::new("Pend", 1)),
+        (
+            "icpr",
+            0x180,
+            "clrpend",
+            pending(),
+            Variant::new("Unpend", 1),
+        ),
+    ] {
+        registers.extend(bank(ident, offset, 32, interrupts, |n| {
+            Field::new(
+                format!("{field}{n}"),
+                (n % 32) as u8,
+                1,
+                Access::read_write_asymmetrical(
+                    Numericity::enumerated(read.clone()),
+                    Numericity::enumerated([Variant::new("Noop", 0).inert(), write.clone()]),
+                ),
+            )
+            // the enables are written by the interrupt masks, and the pending bits by the
+            // interrupt requests
+            .hardware_access(HardwareAccess::Write)
+        }));
+    }
+
+    if !matches!(architecture, Architecture::V6M) {
+        registers.extend(bank("iabr", 0x200, 32, interrupts, |n| {
+            Field::new(
+                format!("active{n}"),
+                (n % 32) as u8,
+                1,
+                Access::read(Numericity::enumerated([
+                    Variant::new("Inactive", 0),
+                    Variant::new("Active", 1),
+                ])),
+            )
+        }));
+    }
+
+    // only the upper priority bits are implemented, so the value read back may differ from the
+    // value written
+    registers.extend(bank("ipr", 0x300, 4, interrupts, |n| {
+        Field::new(
+            format!("pri{n}"),
+            (n % 4 * 8) as u8,
+            8,
+            Access::read_write(Numericity::Numeric),
+        )
+        .hardware_access(HardwareAccess::Write)
+    }));
+
+    Peripheral::new("nvic", 0xe000_e100, registers).docs(["Nested vectored interrupt controller."])
+}
+
+/// The registers holding a field for each of the `interrupts` external interrupts,
+/// `per_register` interrupts at a time.
+fn bank(
+    ident: &str,
+    offset: u32,
+    per_register: usize,
+    interrupts: usize,
+    field: impl Fn(usize) -> Field,
+) -> Vec<Register> {
+    (0..interrupts.div_ceil(per_register))
+        .map(|i| {
+            Register::new(
+                format!("{ident}{i}"),
+                offset + 4 * i as u32,
+                (i * per_register..interrupts.min((i + 1) * per_register)).map(&field),
+            )
+            .reset(0)
+        })
+        .collect()
+}
@@ -0,0 +1,179 @@
+use ir::{
+    access::{Access, HardwareAccess},
+    structures::{
+        cpu::Architecture,
+        field::{Field, Numericity},
+        peripheral::Peripheral,
+        register::Register,
+        variant::Variant,
+    },
+};
+
+use super::switch;
+
+/// The system control block, including the coprocessor access control register when the core
+/// implements a floating point unit.
+pub fn generate(architecture: Architecture, fpu: bool) -> Peripheral {
+    let numeric =
+        |ident, offset, width| Field::new(ident, offset, width, Access::read(Numericity::Numeric));
+
+    // pending bits which are set or cleared by writing one to the corresponding bit
+    let pend = |ident, offset| {
+        Field::new(
+            ident,
+            offset,
+            1,
+            Access::read_write_asymmetrical(
+                Numericity::enumerated([Variant::new("Idle", 0), Variant::new("Pending", 1)]),
+                Numericity::enumerated([Variant::new("Noop", 0).inert(), Variant::new("Pend", 1)]),
+            ),
+        )
+    };
+    let unpend = |ident, offset| {
+        Field::new(
+            ident,
+            offset,
+            1,
+            Access::write(Numericity::enumerated([
+                Variant::new("Noop", 0).inert(),
+                Variant::new("Unpend", 1),
+            ])),
+        )
+    };
+    // only the upper priority bits are implemented, so the value read back may differ from the
+    // value written
+    let priority = |ident, offset| {
+        Field::new(ident, offset, 8, Access::read_write(Numericity::Numeric))
+            .hardware_access(HardwareAccess::Write)
+    };
+
+    let mut registers = vec![
+        Register::new(
+            "cpuid",
+            0x00,
+            [
+                numeric("revision", 0, 4),
+                numeric("partno", 4, 12),
+                numeric("architecture", 16, 4),
+                numeric("variant", 20, 4),
+                numeric("implementer", 24, 8),
+            ],
+        ),
+        Register::new(
+            "icsr",
+            0x04,
+            [
+                numeric("vectactive", 0, 9),
+                numeric("vectpending", 12, 9),
+                numeric("isrpending", 22, 1),
+                unpend("pendstclr", 25),
+                pend("pendstset", 26),
+                unpend("pendsvclr", 27),
+                pend("pendsvset", 28),
+                pend("nmipendset", 31),
+            ],
+        )
+        .reset(0),
+        Register::new(
+            "aircr",
+            0x0c,
+            [
+                Field::new(
+                    "sysresetreq",
+                    2,
+                    1,
+                    Access::write(Numericity::enumerated([
+                        Variant::new("Noop", 0).inert(),
+                        Variant::new("Request", 1),
+                    ])),
+                ),
+                Field::new(
+                    "endianness",
+                    15,
+                    1,
+                    Access::read(Numericity::enumerated([
+                        Variant::new("Little", 0),
+                        Variant::new("Big", 1),
+                    ])),
+                ),
+                Field::new(
+                    "vectkey",
+                    16,
+                    16,
+                    Access::read_write_asymmetrical(Numericity::Numeric, Numericity::Numeric),
+                ),
+            ],
+        ),
+        Register::new(
+            "scr",
+            0x10,
+            [
+                switch("sleeponexit", 1),
+                Field::new(
+                    "sleepdeep",
+                    2,
+                    1,
+                    Access::read_write(Numericity::enumerated([
+                        Variant::new("Sleep", 0),
+                        Variant::new("DeepSleep", 1),
+                    ])),
+                ),
+                switch("sevonpend", 4),
+            ],
+        )
+        .reset(0),
+        Register::new("shpr2", 0x1c, [priority("pri_11", 24)]).reset(0),
+        Register::new(
+            "shpr3",
+            0x20,
+            [priority("pri_14", 16), priority("pri_15", 24)],
+        )
+        .reset(0),
+    ];
+
+    if !matches!(architecture, Architecture::V6M) {
+        registers.extend([
+            Register::new(
+                "vtor",
+                0x08,
+                [Field::new(
+                    "tbloff",
+                    7,
+                    25,
+                    Access::read_write(Numericity::Numeric),
+                )],
+            )
+            .reset(0),
+            Register::new(
+                "shpr1",
+                0x18,
+                [
+                    priority("pri_4", 0),
+                    priority("pri_5", 8),
+                    priority("pri_6", 16),
+                ],
+            )
+            .reset(0),
+        ]);
+    }
+
+    if fpu {
+        let access = |ident, offset| {
+            Field::new(
+                ident,
+                offset,
+                2,
+                Access::read_write(Numericity::enumerated([
+                    Variant::new("Denied", 0),
+                    Variant::new("Privileged", 1),
+                    Variant::new("Full", 3),
+                ])),
+            )
+        };
+
+        registers
+            .push(Register::new("cpacr", 0x88, [access("cp10", 20), access("cp11", 22)]).reset(0));
+    }
+
+    Peripheral::new("scb", 0xe000_ed00, registers).docs(["System control block."])
+}
@@ -0,0 +1,75 @@
+use ir::{
+    access::{Access, HardwareAccess},
+    structures::{
+        field::{Field, Numericity},
+        peripheral::Peripheral,
+        register::Register,
+        variant::Variant,
+    },
+};
+
+use super::switch;
+
+/// The system timer.
+///
+/// *Note: The clock source at reset is implementation defined. The processor clock is assumed,
+/// as on implementations without an external reference clock. The reload and current values
+/// are unknown at reset, so they are not statically tracked.*
+pub fn generate() -> Peripheral {
+    Peripheral::new(
+        "sys_tick",
+        0xe000_e010,
+        [
+            Register::new(
+                "csr",
+                0x0,
+                [
+                    switch("enable", 0),
+                    switch("tickint", 1),
+                    Field::new(
+                        "clksource",
+                        2,
+                        1,
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new("External", 0),
+                            Variant::new("Processor", 1),
+                        ])),
+                    ),
+                    Field::new("countflag", 16, 1, Access::read(Numericity::Numeric)),
+                ],
+            )
+            .reset(0x4),
+            Register::new(
+                "rvr",
+                0x4,
+                [Field::new(
+                    "reload",
+                    0,
+                    24,
+                    Access::read_write_asymmetrical(Numericity::Numeric, Numericity::Numeric),
+                )],
+            ),
+            Register::new(
+                "cvr",
+                0x8,
+                [Field::new(
+                    "current",
+                    0,
+                    24,
+                    Access::read_write_asymmetrical(Numericity::Numeric, Numericity::Numeric),
+                )
+                .hardware_access(HardwareAccess::Write)],
+            ),
+            Register::new(
+                "calib",
+                0xc,
+                [
+                    Field::new("tenms", 0, 24, Access::read(Numericity::Numeric)),
+                    Field::new("skew", 30, 1, Access::read(Numericity::Numeric)),
+                    Field::new("noref", 31, 1, Access::read(Numericity::Numeric)),
+                ],
+            ),
+        ],
+    )
+    .docs(["System timer."])
+}
@@ -1,3 +1,5 @@
 pub mod codegen;
+pub mod cortex_m;
+pub mod svd;
 
 pub use ir;
@@ -0,0 +1,46 @@
+//! Extraction of model cross-check data from vendor SVD files.
+
+use std::collections::HashMap;
+
+/// The interrupt numbers declared by an SVD, keyed by interrupt name.
+///
+/// *Note: Only the `<interrupt>` elements are inspected, so the SVD is not otherwise validated.*
+pub fn interrupts(svd: &str) -> HashMap<String, usize> {
+    let mut interrupts = HashMap::new();
+    let mut rest = svd;
+
+    while let Some(start) = rest.find("<interrupt>") {
+        rest = &rest[start + "<interrupt>".len()..];
+
+        let Some(end) = rest.find("</interrupt>") else {
+            break;
+        };
+        let element = &rest[..end];
+        rest = &rest[end..];
+
+        if let (Some(name), Some(value)) = (text(element, "name"), text(element, "value"))
+            && let Some(value) = integer(value)
+        {
+            interrupts.insert(name.to_string(), value);
+        }
+    }
+
+    interrupts
+}
+
+/// The trimmed text of the first `tag` element within `element`.
+fn text<'a>(element: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{tag}>");
+    let start = element.find(&open)? + open.len();
+    let end = element[start..].find(&format!("</{tag}>"))? + start;
+
+    Some(element[start..end].trim())
+}
+
+/// Parse an SVD integer, which may be decimal or hexadecimal.
+fn integer(text: &str) -> Option<usize> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
@@ -0,0 +1,19 @@
+//! Adopting peripheral state from hardware.
+//!
+//! When control is handed over from a bootloader (or any other prior firmware), the peripherals
+//! are generally not in the reset state. Generated HALs provide `adopt` functions which read the
+//! live registers and reconstruct the peripheral states from them instead.
+//!
+//! Fields holding values which do not correspond to any modeled variant are reported with
+//! [`Unmodeled`].
+
+/// A field held a value which does not correspond to any modeled variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Unmodeled {
+    pub peripheral: &'static str,
+    pub register: &'static str,
+    pub field: &'static str,
+    /// The bits held by the field.
+    pub bits: u32,
+}
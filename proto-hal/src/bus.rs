@@ -0,0 +1,35 @@
+//! Register access of external devices.
+//!
+//! HALs generated for devices behind a bus (such as I²C or SPI register devices) perform every
+//! register access through an implementor of [`Bus`] (or [`AsyncBus`]) supplied by the user.
+//!
+//! Addresses are device register addresses, and buffers hold exactly one register word in the
+//! byte order of the device.
+
+use core::future::Future;
+
+/// A blocking bus through which device registers are accessed.
+pub trait Bus {
+    type Error;
+
+    /// Read the register at `address` into `buf`.
+    fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Write `buf` to the register at `address`.
+    fn write(&mut self, address: u32, buf: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// An asynchronous bus through which device registers are accessed.
+pub trait AsyncBus {
+    type Error;
+
+    /// Read the register at `address` into `buf`.
+    fn read(
+        &mut self,
+        address: u32,
+        buf: &mut [u8],
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+
+    /// Write `buf` to the register at `address`.
+    fn write(&mut self, address: u32, buf: &[u8]) -> impl Future<Output = Result<(), Self::Error>>;
+}
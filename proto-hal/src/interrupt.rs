@@ -1,8 +1,12 @@
+use core::{cell::RefCell, marker::PhantomData};
+
+use critical_section::Mutex;
+
 /// Represents a vector table entry, i.e.
 /// a function pointer.
 ///
 /// *Note: An empty entry (reserved) is the null pointer.*
-pub struct Vector(#[allow(unused)] *const ());
+pub struct Vector(*const ());
 
 impl Vector {
     /// Create a vector with the provided function pointer.
@@ -20,5 +24,201 @@ impl Vector {
 ///
 /// This impl is needed due to the underlying
 /// `*const ()` value. This value is never
-/// read, and as such is `Sync`.
+/// written, and as such is `Sync`.
 unsafe impl Sync for Vector {}
+
+/// Invoke the handler at `position` of the vector `table`, or `default` if the position is reserved
+/// or beyond the table.
+///
+/// *Note: This dispatches the interrupts multiplexed by an external interrupt controller, such as the
+/// PLIC or CLIC of riscv-rt, whose trap handler only provides the position of the interrupt.*
+///
+/// # Safety
+/// The handlers may only be invoked from the trap handler of the interrupt controller.
+pub unsafe fn dispatch(table: &[Vector], position: usize, default: unsafe extern "C" fn()) {
+    match table.get(position).filter(|vector| !vector.0.is_null()) {
+        // SAFETY: non-null vectors are only created from handlers
+        Some(vector) => unsafe {
+            core::mem::transmute::<*const (), unsafe extern "C" fn()>(vector.0)()
+        },
+        None => unsafe { default() },
+    }
+}
+
+/// The address of the first NVIC interrupt set-enable register.
+const NVIC_ISER: usize = 0xe000_e100;
+
+/// The address of the first NVIC interrupt clear-enable register.
+const NVIC_ICER: usize = 0xe000_e180;
+
+/// Unmask the interrupt at `position` of the vector table in the NVIC.
+///
+/// *Note: With the `sim` feature, the NVIC of an installed simulator is modified instead.*
+///
+/// # Safety
+/// Unmasking an interrupt may break critical sections based on masking the interrupt.
+pub unsafe fn unmask(position: u16) {
+    #[cfg(feature = "sim")]
+    if crate::sim::set_unmasked(position, true) {
+        return;
+    }
+
+    unsafe {
+        core::ptr::write_volatile(
+            (NVIC_ISER + 4 * (position as usize / 32)) as *mut u32,
+            1 << (position % 32),
+        )
+    };
+}
+
+/// Mask the interrupt at `position` of the vector table in the NVIC.
+///
+/// *Note: With the `sim` feature, the NVIC of an installed simulator is modified instead.*
+pub fn mask(position: u16) {
+    #[cfg(feature = "sim")]
+    if crate::sim::set_unmasked(position, false) {
+        return;
+    }
+
+    unsafe {
+        core::ptr::write_volatile(
+            (NVIC_ICER + 4 * (position as usize / 32)) as *mut u32,
+            1 << (position % 32),
+        )
+    };
+}
+
+/// Resources moved into the context of an interrupt handler.
+///
+/// `I` is the NVIC mask state of the interrupt, so a binding can only be bound to (and unbound
+/// from) the interrupt it was declared for. Bindings are declared with [`bind!`](crate::bind).
+pub struct Binding<I, R> {
+    resources: Mutex<RefCell<Option<R>>>,
+    _interrupt: PhantomData<fn() -> I>,
+}
+
+impl<I, R> Binding<I, R> {
+    pub const fn new() -> Self {
+        Self {
+            resources: Mutex::new(RefCell::new(None)),
+            _interrupt: PhantomData,
+        }
+    }
+
+    /// Move `resources` into the binding, returning the resources previously bound (if any).
+    ///
+    /// *Note: The mask state witnesses that the handler is not running while it is rebound.*
+    pub fn bind(&self, #[expect(unused)] masked: &I, resources: R) -> Option<R> {
+        critical_section::with(|cs| self.resources.borrow_ref_mut(cs).replace(resources))
+    }
+
+    /// Move the resources out of the binding.
+    ///
+    /// *Note: The mask state witnesses that the handler is not running while it is unbound.*
+    pub fn unbind(&self, #[expect(unused)] masked: &I) -> Option<R> {
+        critical_section::with(|cs| self.resources.borrow_ref_mut(cs).take())
+    }
+
+    /// Run the handler `f` with the bound resources (if any).
+    ///
+    /// *Note: The resources are accessed within a critical section, so the handler cannot be
+    /// preempted while the resources are borrowed.*
+    ///
+    /// # Safety
+    /// Must only be called by the interrupt handler declared with [`bind!`](crate::bind), as the
+    /// resources are otherwise accessed outside of the handler they were bound to.
+    #[doc(hidden)]
+    pub unsafe fn handle(&self, f: impl FnOnce(&mut R)) {
+        critical_section::with(|cs| {
+            if let Some(resources) = self.resources.borrow_ref_mut(cs).as_mut() {
+                f(resources);
+            }
+        });
+    }
+}
+
+impl<I, R> Default for Binding<I, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Declare the handler of an interrupt along with the resources it requires.
+///
+/// The resources are moved into the handler context when the interrupt is bound with the
+/// generated `interrupts::<interrupt>::Masked::bind`, and moved back out with
+/// `Unmasked::unbind`. The handler does nothing while no resources are bound.
+///
+/// *Note: The handler body runs inside a global critical section (as the resources are shared
+/// with the binding), so all other interrupts are blocked until it returns.*
+///
+/// ```ignore
+/// proto_hal::bind! {
+///     CORDIC in g4: cordic::rdata::res0::Dynamic => |res0| {
+///         let _ = cordic::rdata::read().res0(res0);
+///     }
+/// }
+///
+/// let (cordic, _) = unsafe { p.interrupts.CORDIC.bind(&CORDIC::BINDING, res0, cordic_entitlement, ien) };
+/// ```
+#[macro_export]
+macro_rules! bind {
+    ($interrupt:ident in $($hal:ident)::+: $resources:ty => |$binding:pat_param| $body:block) => {
+        #[allow(non_snake_case)]
+        pub mod $interrupt {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// The resources bound to the interrupt handler.
+            pub static BINDING: $crate::interrupt::Binding<
+                $($hal)::+::interrupts::$interrupt::Masked,
+                $resources,
+            > = $crate::interrupt::Binding::new();
+
+            /// The interrupt handler.
+            ///
+            /// # Safety
+            /// Must only be called by the interrupt vector.
+            #[unsafe(no_mangle)]
+            pub unsafe extern "C" fn $interrupt() {
+                // the body is declared outside of the unsafe block, so it is not unsafe itself
+                let handler = |$binding: &mut $resources| $body;
+
+                unsafe { BINDING.handle(handler) };
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{Vector, dispatch};
+
+    /// The handler which was invoked last.
+    static INVOKED: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C" fn handler() {
+        INVOKED.store(1, Ordering::Relaxed);
+    }
+
+    unsafe extern "C" fn default_handler() {
+        INVOKED.store(2, Ordering::Relaxed);
+    }
+
+    /// Dispatch interrupts from a table with a reserved position.
+    ///
+    /// Expected behavior: The handler is invoked for its position, and the default handler for the
+    /// reserved position and positions beyond the table.
+    #[test]
+    fn dispatch_table() {
+        let table = [Vector::reserved(), Vector::handler(handler)];
+
+        for (position, expected) in [(0, 2), (1, 1), (2, 2)] {
+            unsafe { dispatch(&table, position, default_handler) };
+
+            assert_eq!(INVOKED.swap(0, Ordering::Relaxed), expected);
+        }
+    }
+}
@@ -1,5 +1,10 @@
 #![no_std]
 
+#[cfg(any(feature = "sim", feature = "trace"))]
+extern crate std;
+
+pub mod adopt;
+pub mod bus;
 pub mod gpio;
 
 #[cfg(feature = "stm32")]
@@ -7,11 +12,26 @@ pub mod stm32;
 
 pub mod interrupt;
 pub mod ir_utils;
+pub mod memory;
 pub mod prelude;
+#[cfg(feature = "sim")]
+pub mod sim;
 pub mod stasis;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod wait;
 
 pub use critical_section;
 
+/// The reasons the peripherals of a HAL cannot be taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TakeError {
+    /// The peripherals have not been declared to be in the reset state on startup.
+    Undeclared,
+    /// The peripherals have already been taken.
+    Taken,
+}
+
 /// Types that encapsulate a resource that can be configured to be
 /// in a "reset" state implement this trait.
 pub trait IntoReset {
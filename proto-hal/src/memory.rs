@@ -0,0 +1,25 @@
+//! Memory regions of a device.
+//!
+//! Generated HALs describe the regions of the device memory map in their `memory` module.
+
+/// A region of the device memory map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Region {
+    /// The address of the start of the region.
+    pub origin: usize,
+    /// The length of the region in bytes.
+    pub length: usize,
+}
+
+impl Region {
+    /// The address one past the end of the region.
+    pub const fn end(&self) -> usize {
+        self.origin + self.length
+    }
+
+    /// Whether the address lies within the region.
+    pub const fn contains(&self, address: usize) -> bool {
+        self.origin <= address && address < self.end()
+    }
+}
@@ -0,0 +1,350 @@
+//! A host-side behavioral register simulator.
+//!
+//! Generated HALs (with the `sim` feature enabled) emit a static model of every peripheral,
+//! register, and field. A [`Simulator`] built from those models holds a register file which
+//! behaves like hardware with respect to field access and reset values:
+//!
+//! - Registers begin with their reset value (or zero if none is specified).
+//! - Software writes only affect writable fields.
+//! - Software reads of write-only fields and reserved bits yield zero.
+//!
+//! Hardware-side behavior (such as status flags being set or cleared) is scripted with
+//! hooks which run on every software access of a register.
+//!
+//! *Note: The model does not yet express access effects, so effects like clear-on-write
+//! must be scripted with hooks.*
+//!
+//! Once [installed](Simulator::install), all register accesses performed by the generated
+//! HAL on the current thread are routed to the simulator. Interrupt masking through the NVIC
+//! (see [`crate::interrupt::unmask`]) is simulated as well.
+
+use std::{
+    boxed::Box,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    thread_local,
+    vec::Vec,
+};
+
+/// The static model of a field.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldModel {
+    pub ident: &'static str,
+    pub offset: u8,
+    pub width: u8,
+    /// Whether software can read the field.
+    pub readable: bool,
+    /// Whether software can write the field.
+    pub writable: bool,
+}
+
+impl FieldModel {
+    pub const fn mask(&self) -> u32 {
+        (u32::MAX >> (32 - self.width as u32)) << self.offset
+    }
+}
+
+/// The static model of a register.
+#[derive(Debug, Clone, Copy)]
+pub struct RegisterModel {
+    pub ident: &'static str,
+    pub offset: u32,
+    pub reset: Option<u32>,
+    pub fields: &'static [FieldModel],
+}
+
+impl RegisterModel {
+    fn mask(&self, f: impl Fn(&FieldModel) -> bool) -> u32 {
+        self.fields
+            .iter()
+            .filter(|field| f(field))
+            .fold(0, |mask, field| mask | field.mask())
+    }
+
+    /// The bits software can read.
+    pub fn read_mask(&self) -> u32 {
+        self.mask(|field| field.readable)
+    }
+
+    /// The bits software can write.
+    pub fn write_mask(&self) -> u32 {
+        self.mask(|field| field.writable)
+    }
+
+    fn field(&self, ident: &str) -> &'static FieldModel {
+        self.fields
+            .iter()
+            .find(|field| field.ident == ident)
+            .unwrap_or_else(|| panic!("register [{}] has no field [{ident}]", self.ident))
+    }
+}
+
+/// The static model of a peripheral.
+#[derive(Debug, Clone, Copy)]
+pub struct PeripheralModel {
+    pub ident: &'static str,
+    pub base_addr: u32,
+    pub registers: &'static [RegisterModel],
+}
+
+/// A software write to a simulated register.
+#[derive(Debug, Clone, Copy)]
+pub struct WriteEvent {
+    /// The value of the register prior to the write.
+    pub previous: u32,
+    /// The value written by software.
+    pub written: u32,
+}
+
+type ReadHook = Box<dyn FnMut(&mut u32)>;
+type WriteHook = Box<dyn FnMut(&mut u32, WriteEvent)>;
+
+struct SimulatedRegister {
+    model: &'static RegisterModel,
+    value: u32,
+    read_hooks: Vec<ReadHook>,
+    write_hooks: Vec<WriteHook>,
+}
+
+/// A simulated register file.
+pub struct Simulator {
+    peripherals: &'static [PeripheralModel],
+    registers: HashMap<(&'static str, u32), SimulatedRegister>,
+    unmasked: HashSet<u16>,
+}
+
+thread_local! {
+    static SIMULATOR: RefCell<Option<Simulator>> = const { RefCell::new(None) };
+}
+
+impl Simulator {
+    /// Create a simulator with all registers in their reset state.
+    pub fn new(peripherals: &'static [PeripheralModel]) -> Self {
+        let registers = peripherals
+            .iter()
+            .flat_map(|peripheral| {
+                peripheral.registers.iter().map(|register| {
+                    (
+                        (peripheral.ident, register.offset),
+                        SimulatedRegister {
+                            model: register,
+                            value: register.reset.unwrap_or(0),
+                            read_hooks: Vec::new(),
+                            write_hooks: Vec::new(),
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        Self {
+            peripherals,
+            registers,
+            unmasked: HashSet::new(),
+        }
+    }
+
+    fn register_mut(&mut self, peripheral: &str, register: &str) -> &mut SimulatedRegister {
+        let peripheral = self
+            .peripherals
+            .iter()
+            .find(|model| model.ident == peripheral)
+            .unwrap_or_else(|| panic!("no peripheral [{peripheral}] is simulated"));
+
+        let offset = peripheral
+            .registers
+            .iter()
+            .find(|model| model.ident == register)
+            .unwrap_or_else(|| {
+                panic!(
+                    "peripheral [{}] has no register [{register}]",
+                    peripheral.ident
+                )
+            })
+            .offset;
+
+        self.registers
+            .get_mut(&(peripheral.ident, offset))
+            .expect("all modeled registers are simulated")
+    }
+
+    /// Add a hook which runs before every software read of the register.
+    ///
+    /// The hook may modify the register value to emulate hardware-side changes.
+    pub fn on_read(
+        mut self,
+        peripheral: &str,
+        register: &str,
+        hook: impl FnMut(&mut u32) + 'static,
+    ) -> Self {
+        self.register_mut(peripheral, register)
+            .read_hooks
+            .push(Box::new(hook));
+        self
+    }
+
+    /// Add a hook which runs after every software write of the register.
+    ///
+    /// The hook may modify the register value to emulate hardware-side changes.
+    pub fn on_write(
+        mut self,
+        peripheral: &str,
+        register: &str,
+        hook: impl FnMut(&mut u32, WriteEvent) + 'static,
+    ) -> Self {
+        self.register_mut(peripheral, register)
+            .write_hooks
+            .push(Box::new(hook));
+        self
+    }
+
+    /// View the raw value held by the register, bypassing access rules and hooks.
+    pub fn peek(&mut self, peripheral: &str, register: &str) -> u32 {
+        self.register_mut(peripheral, register).value
+    }
+
+    /// Place a raw value into the register, bypassing access rules and hooks.
+    pub fn poke(&mut self, peripheral: &str, register: &str, value: u32) {
+        self.register_mut(peripheral, register).value = value;
+    }
+
+    /// View the value held by a field, bypassing access rules and hooks.
+    pub fn field(&mut self, peripheral: &str, register: &str, field: &str) -> u32 {
+        let register = self.register_mut(peripheral, register);
+        let field = register.model.field(field);
+
+        (register.value & field.mask()) >> field.offset
+    }
+
+    /// Place a value into a field (as hardware would), bypassing access rules and hooks.
+    pub fn set_field(&mut self, peripheral: &str, register: &str, field: &str, value: u32) {
+        let register = self.register_mut(peripheral, register);
+        let field = register.model.field(field);
+
+        register.value =
+            (register.value & !field.mask()) | ((value << field.offset) & field.mask());
+    }
+
+    /// Whether the interrupt at `position` of the vector table is unmasked in the NVIC.
+    pub fn is_unmasked(&self, position: u16) -> bool {
+        self.unmasked.contains(&position)
+    }
+
+    /// Restore all registers to their reset state, and mask all interrupts. Hooks are retained.
+    pub fn reset(&mut self) {
+        for register in self.registers.values_mut() {
+            register.value = register.model.reset.unwrap_or(0);
+        }
+
+        self.unmasked.clear();
+    }
+
+    /// Install the simulator for the current thread.
+    ///
+    /// The simulator is uninstalled when the returned guard is dropped.
+    pub fn install(self) -> Installed {
+        SIMULATOR.with_borrow_mut(|simulator| {
+            assert!(
+                simulator.is_none(),
+                "a simulator is already installed on this thread"
+            );
+
+            *simulator = Some(self);
+        });
+
+        Installed { _private: () }
+    }
+}
+
+/// A guard representing an installed [`Simulator`].
+pub struct Installed {
+    _private: (),
+}
+
+impl Installed {
+    /// Access the installed simulator.
+    pub fn with<R>(&self, f: impl FnOnce(&mut Simulator) -> R) -> R {
+        SIMULATOR.with_borrow_mut(|simulator| {
+            f(simulator
+                .as_mut()
+                .expect("the simulator is installed while the guard lives"))
+        })
+    }
+}
+
+impl Drop for Installed {
+    fn drop(&mut self) {
+        SIMULATOR.with_borrow_mut(|simulator| simulator.take());
+    }
+}
+
+/// Perform a software read of a register.
+///
+/// Returns [`None`] if no simulator is installed or the register is not simulated.
+#[doc(hidden)]
+pub fn read(peripheral: &'static str, offset: u32) -> Option<u32> {
+    SIMULATOR.with_borrow_mut(|simulator| {
+        let register = simulator
+            .as_mut()?
+            .registers
+            .get_mut(&(peripheral, offset))?;
+
+        for hook in &mut register.read_hooks {
+            hook(&mut register.value);
+        }
+
+        Some(register.value & register.model.read_mask())
+    })
+}
+
+/// Perform a software write of a register.
+///
+/// Returns `false` if no simulator is installed or the register is not simulated.
+#[doc(hidden)]
+pub fn write(peripheral: &'static str, offset: u32, value: u32) -> bool {
+    SIMULATOR.with_borrow_mut(|simulator| {
+        let Some(register) = simulator
+            .as_mut()
+            .and_then(|simulator| simulator.registers.get_mut(&(peripheral, offset)))
+        else {
+            return false;
+        };
+
+        let previous = register.value;
+        let mask = register.model.write_mask();
+
+        register.value = (previous & !mask) | (value & mask);
+
+        for hook in &mut register.write_hooks {
+            hook(
+                &mut register.value,
+                WriteEvent {
+                    previous,
+                    written: value,
+                },
+            );
+        }
+
+        true
+    })
+}
+
+/// Unmask (or mask) an interrupt in the NVIC.
+///
+/// Returns `false` if no simulator is installed.
+#[doc(hidden)]
+pub fn set_unmasked(position: u16, unmasked: bool) -> bool {
+    SIMULATOR.with_borrow_mut(|simulator| {
+        let Some(simulator) = simulator.as_mut() else {
+            return false;
+        };
+
+        if unmasked {
+            simulator.unmasked.insert(position);
+        } else {
+            simulator.unmasked.remove(&position);
+        }
+
+        true
+    })
+}
@@ -0,0 +1,163 @@
+//! Register access trace recording.
+//!
+//! Generated HALs (with the `trace` feature enabled) record every register access performed
+//! through the unsafe interface (which the type-state interface is built upon) while a
+//! [`Recording`] is active on the current thread.
+//!
+//! The recorded [`Trace`] can be asserted against to verify the exact sequence of accesses
+//! an operation compiles down to.
+
+use std::{cell::RefCell, thread_local, vec::Vec};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Read,
+    Write,
+}
+
+/// A single register access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Access {
+    pub peripheral: &'static str,
+    pub register: &'static str,
+    pub kind: Kind,
+    /// The value read from or written to the register.
+    pub value: u32,
+    /// Whether the access was performed by a read-modify-write holding a critical section
+    /// (i.e. `modify_untracked`, which `modify` and `modify_in_cs` are built upon).
+    ///
+    /// *Note: The critical section state itself is not observable, so accesses performed through
+    /// other functions are never flagged, even when performed within a critical section.*
+    pub via_modify_in_cs: bool,
+}
+
+impl Access {
+    /// Whether this access is to the specified register.
+    pub fn is(&self, peripheral: &str, register: &str) -> bool {
+        self.peripheral == peripheral && self.register == register
+    }
+}
+
+/// A sequence of register accesses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    accesses: Vec<Access>,
+}
+
+impl Trace {
+    pub fn accesses(&self) -> &[Access] {
+        &self.accesses
+    }
+
+    /// The accesses performed on the specified register.
+    pub fn of<'a>(
+        &'a self,
+        peripheral: &'a str,
+        register: &'a str,
+    ) -> impl Iterator<Item = &'a Access> + 'a {
+        self.accesses
+            .iter()
+            .filter(move |access| access.is(peripheral, register))
+    }
+
+    /// The position of the first access of the specified kind to the specified register.
+    pub fn position(&self, peripheral: &str, register: &str, kind: Kind) -> Option<usize> {
+        self.accesses
+            .iter()
+            .position(|access| access.is(peripheral, register) && access.kind == kind)
+    }
+
+    /// Accesses which are redundant with the previous access to the same register.
+    ///
+    /// A read is redundant if the register has been read since it was last written, and a
+    /// write is redundant if it writes the value most recently written to the register.
+    pub fn redundant(&self) -> Vec<&Access> {
+        let mut redundant = Vec::new();
+
+        for (i, access) in self.accesses.iter().enumerate() {
+            let Some(previous) = self.accesses[..i]
+                .iter()
+                .rev()
+                .find(|previous| previous.is(access.peripheral, access.register))
+            else {
+                continue;
+            };
+
+            let is_redundant = match access.kind {
+                Kind::Read => previous.kind == Kind::Read,
+                Kind::Write => self.accesses[..i]
+                    .iter()
+                    .rev()
+                    .find(|previous| {
+                        previous.is(access.peripheral, access.register)
+                            && previous.kind == Kind::Write
+                    })
+                    .is_some_and(|previous| previous.value == access.value),
+            };
+
+            if is_redundant {
+                redundant.push(access);
+            }
+        }
+
+        redundant
+    }
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<Trace>> = const { RefCell::new(None) };
+}
+
+/// A guard representing an active recording.
+///
+/// Recording stops when the guard is dropped.
+pub struct Recording {
+    _private: (),
+}
+
+impl Recording {
+    /// Begin recording register accesses on the current thread.
+    pub fn start() -> Self {
+        TRACE.with_borrow_mut(|trace| {
+            assert!(
+                trace.is_none(),
+                "a recording is already active on this thread"
+            );
+
+            *trace = Some(Trace::default());
+        });
+
+        Self { _private: () }
+    }
+
+    /// Take the accesses recorded so far, clearing the recording.
+    pub fn take(&self) -> Trace {
+        TRACE.with_borrow_mut(|trace| {
+            trace
+                .as_mut()
+                .map(core::mem::take)
+                .expect("the recording is active while the guard lives")
+        })
+    }
+
+    /// Stop recording and retrieve the trace.
+    pub fn finish(self) -> Trace {
+        self.take()
+    }
+}
+
+impl Drop for Recording {
+    fn drop(&mut self) {
+        TRACE.with_borrow_mut(|trace| trace.take());
+    }
+}
+
+/// Record a register access if a recording is active.
+#[doc(hidden)]
+pub fn record(access: Access) {
+    TRACE.with_borrow_mut(|trace| {
+        if let Some(trace) = trace {
+            trace.accesses.push(access);
+        }
+    });
+}
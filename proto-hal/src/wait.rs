@@ -0,0 +1,57 @@
+//! Waiting for hardware-updated fields.
+//!
+//! Generated HALs provide `wait_until_<variant>` functions for fields written by hardware
+//! (such as status flags), which block until the field holds the variant. Fields designated
+//! with an interrupt additionally provide `wait_until_<variant>_async` futures, which are woken
+//! when the HAL's `wake::<interrupt>` function is called from the interrupt handler.
+
+use core::{cell::RefCell, task};
+
+use critical_section::Mutex;
+
+/// The iteration bound of a blocking wait was reached before the field held the variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(::defmt::Format))]
+pub struct Timeout;
+
+/// Storage for the waker of a task waiting on a field.
+///
+/// Only one task can wait on a field at a time. Registering a waker replaces the previous one.
+pub struct Waker {
+    waker: Mutex<RefCell<Option<task::Waker>>>,
+}
+
+impl Waker {
+    pub const fn new() -> Self {
+        Self {
+            waker: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Register the waker of the task to be woken.
+    pub fn register(&self, waker: &task::Waker) {
+        critical_section::with(|cs| {
+            let mut slot = self.waker.borrow_ref_mut(cs);
+
+            if !slot
+                .as_ref()
+                .is_some_and(|current| current.will_wake(waker))
+            {
+                *slot = Some(waker.clone());
+            }
+        });
+    }
+
+    /// Wake the registered task (if any).
+    pub fn wake(&self) {
+        if let Some(waker) = critical_section::with(|cs| self.waker.borrow_ref_mut(cs).take()) {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for Waker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
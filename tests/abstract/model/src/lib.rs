@@ -1,18 +1,18 @@
 use proto_hal_build::ir::{
-    access::{Access, AccessProperties},
+    access::{Access, AccessProperties, HardwareAccess},
     structures::{
         entitlement::Entitlement,
         field::{Field, Numericity},
         hal::Hal,
+        interrupts::Interrupt,
         peripheral::Peripheral,
         register::Register,
         variant::Variant,
     },
-    utils::diagnostic::Diagnostics,
 };
 
-pub fn generate() -> (Hal, Diagnostics) {
-    let hal = Hal::new([
+pub fn generate() -> Hal {
+    Hal::new([
         Peripheral::new(
             "foo",
             0,
@@ -54,6 +54,30 @@ pub fn generate() -> (Hal, Diagnostics) {
                         ),
                     ],
                 ),
+                Register::new(
+                    "foo2",
+                    8,
+                    [
+                        Field::new(
+                            "c",
+                            0,
+                            1,
+                            Access::read_write(Numericity::enumerated([
+                                Variant::new("Off", 0),
+                                Variant::new("On", 1),
+                            ])),
+                        ),
+                        Field::new("d", 8, 8, Access::read_write(Numericity::Numeric))
+                            .hardware_access(HardwareAccess::Write),
+                    ],
+                )
+                .reset(0x0000_0501),
+                Register::new(
+                    "foo3",
+                    12,
+                    [Field::new("e", 0, 8, Access::write(Numericity::Numeric))],
+                )
+                .reset(0x2a),
             ],
         ),
         Peripheral::new(
@@ -61,18 +85,90 @@ pub fn generate() -> (Hal, Diagnostics) {
             0x100,
             [Register::new("bar0", 0, []), Register::new("bar1", 4, [])],
         ),
-    ]);
-
-    let diagnostics = hal.validate();
-
-    (hal, diagnostics)
+        Peripheral::new(
+            "baz",
+            0x200,
+            [
+                Register::new(
+                    "baz0",
+                    0,
+                    [
+                        Field::new(
+                            "mode",
+                            0,
+                            2,
+                            Access::write(Numericity::enumerated(
+                                (0..3).map(|i| Variant::new(format!("M{i}"), i)),
+                            )),
+                        ),
+                        Field::new("level", 8, 8, Access::write(Numericity::Numeric)),
+                    ],
+                )
+                .reset(0x0000_2001)
+                .shadowed(),
+                Register::new(
+                    "baz1",
+                    4,
+                    [Field::new(
+                        "status",
+                        0,
+                        1,
+                        Access::read(Numericity::enumerated([
+                            Variant::new("Busy", 0),
+                            Variant::new("Ready", 1),
+                        ])),
+                    )
+                    .wake_from("BAZ")],
+                ),
+            ],
+        ),
+        Peripheral::new(
+            "qux",
+            0x300,
+            [Register::new(
+                "qux0",
+                0,
+                [Field::new(
+                    "ie",
+                    0,
+                    1,
+                    Access::read_write(Numericity::enumerated([
+                        Variant::new("Disabled", 0),
+                        Variant::new("Enabled", 1),
+                    ])),
+                )],
+            )
+            .reset(0)],
+        )
+        .entitlements([Entitlement::to("foo::foo2::c::On")]),
+    ])
+    .interrupts([
+        Interrupt::handler("BAZ")
+            .sources(["baz"])
+            .status(Entitlement::to("baz::baz1::status::Ready")),
+        Interrupt::handler("QUX")
+            .sources(["qux"])
+            .enable(Entitlement::to("qux::qux0::ie::Enabled")),
+    ])
 }
 
 #[cfg(test)]
 mod tests {
     mod hal {
         use proto_hal_build::ir::{
-            structures::{hal::Hal, peripheral::Peripheral, register::Register},
+            access::Access,
+            bus::{Bus, Endianness, Word},
+            structures::{
+                cpu::{Core, Cpu},
+                entitlement::Entitlement,
+                field::{Field, Numericity},
+                hal::Hal,
+                interrupts::{Interrupt, Runtime},
+                memory::Region,
+                peripheral::Peripheral,
+                register::Register,
+                variant::Variant,
+            },
             utils::diagnostic,
         };
 
@@ -83,9 +179,9 @@ mod tests {
 
             assert!(hal.peripherals.is_empty());
 
-            let diagnostics = hal.validate();
+            let hal = hal.validate().unwrap();
 
-            assert!(diagnostics.is_empty());
+            assert!(hal.warnings().is_empty());
         }
 
         /// Create a HAL with one peripheral.
@@ -95,9 +191,9 @@ mod tests {
 
             assert_eq!(hal.peripherals.len(), 1);
 
-            let diagnostics = hal.validate();
+            let hal = hal.validate().unwrap();
 
-            assert!(diagnostics.is_empty());
+            assert!(hal.warnings().is_empty());
         }
 
         /// Create a HAL with many disjoint peripherals.
@@ -113,9 +209,9 @@ mod tests {
 
             assert_eq!(hal.peripherals.len(), 5);
 
-            let diagnostics = hal.validate();
+            let hal = hal.validate().unwrap();
 
-            assert!(diagnostics.is_empty());
+            assert!(hal.warnings().is_empty());
         }
 
         /// Create a HAL with multiple peripherals with the same identifier.
@@ -139,9 +235,9 @@ mod tests {
 
             assert_eq!(hal.peripherals.len(), 2);
 
-            let diagnostics = hal.validate();
+            let hal = hal.validate().unwrap();
 
-            assert!(diagnostics.is_empty());
+            assert!(hal.warnings().is_empty());
         }
 
         /// Create a HAL with multiple peripherals with overlapping domains.
@@ -154,7 +250,7 @@ mod tests {
                 Peripheral::new("bar", 0, [Register::new("bar0", 0, [])]),
             ]);
 
-            let mut diagnostics = hal.validate().into_iter();
+            let mut diagnostics = hal.validate().unwrap_err().into_iter();
 
             let diagnostic = diagnostics.next().unwrap();
 
@@ -162,11 +258,452 @@ mod tests {
             // TODO: match exact diagnostic kind
             assert!(diagnostics.next().is_none());
         }
+
+        /// Create a HAL with a field woken from an interrupt which does not exist.
+        ///
+        /// Expected behavior: Exactly one `unknown-interrupt` error is emitted.
+        #[test]
+        fn wake_from_unknown_interrupt() {
+            let hal = Hal::new([Peripheral::new(
+                "foo",
+                0,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new(
+                        "a",
+                        0,
+                        1,
+                        Access::read(Numericity::enumerated([
+                            Variant::new("Busy", 0),
+                            Variant::new("Ready", 1),
+                        ])),
+                    )
+                    .wake_from("FOO")],
+                )],
+            )]);
+
+            let mut diagnostics = hal.validate().unwrap_err().into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("unknown-interrupt"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create a HAL with an interrupt raised by a peripheral which does not exist, and
+        /// flagged by a field of another peripheral.
+        ///
+        /// Expected behavior: Exactly one `interrupt-source-missing` and one
+        /// `interrupt-field-foreign` error are emitted.
+        #[test]
+        fn interrupt_association() {
+            let hal = Hal::new([Peripheral::new(
+                "foo",
+                0,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new(
+                        "a",
+                        0,
+                        1,
+                        Access::read(Numericity::enumerated([
+                            Variant::new("Busy", 0),
+                            Variant::new("Ready", 1),
+                        ])),
+                    )],
+                )],
+            )])
+            .interrupts([Interrupt::handler("BAR")
+                .sources(["bar"])
+                .status(Entitlement::to("foo::foo0::a::Ready"))]);
+
+            let diagnostics = hal.validate().unwrap_err();
+
+            let mut codes = diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.code().clone().unwrap_or_default())
+                .collect::<Vec<_>>();
+            codes.sort();
+
+            assert_eq!(
+                codes,
+                ["interrupt-field-foreign", "interrupt-source-missing"]
+            );
+        }
+
+        /// Create a bus-backed HAL with consecutive device register addresses.
+        ///
+        /// Expected behavior: Memory-mapped layout rules do not apply, so validation succeeds.
+        #[test]
+        fn bus_consecutive_registers() {
+            let hal = Hal::new([
+                Peripheral::new(
+                    "foo",
+                    1,
+                    [Register::new("foo0", 0, []), Register::new("foo1", 1, [])],
+                ),
+                Peripheral::new("bar", 3, [Register::new("bar0", 0, [])]),
+            ])
+            .bus(Bus::new(Word::U8));
+
+            let hal = hal.validate().unwrap();
+
+            assert!(hal.warnings().is_empty());
+        }
+
+        /// Create a bus-backed HAL with two registers at the same device address.
+        ///
+        /// Expected behavior: Exactly one `bus-address-collision` error is emitted.
+        #[test]
+        fn bus_address_collision() {
+            let hal = Hal::new([
+                Peripheral::new("foo", 0, [Register::new("foo0", 2, [])]),
+                Peripheral::new("bar", 1, [Register::new("bar0", 1, [])]),
+            ])
+            .bus(Bus::new(Word::U8));
+
+            let mut diagnostics = hal.validate().unwrap_err().into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert!(matches!(diagnostic.kind(), diagnostic::Kind::Error));
+            assert_eq!(diagnostic.code().as_deref(), Some("bus-address-collision"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create a bus-backed HAL with a field and reset value exceeding the bus word.
+        ///
+        /// Expected behavior: Two `bus-word-exceeded` errors are emitted.
+        #[test]
+        fn bus_word_exceeded() {
+            let hal = Hal::new([Peripheral::new(
+                "foo",
+                0,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new("a", 4, 8, Access::read(Numericity::Numeric))],
+                )
+                .reset(0x100)],
+            )])
+            .bus(Bus::new(Word::U8));
+
+            let diagnostics = hal.validate().unwrap_err();
+
+            assert_eq!(diagnostics.len(), 2);
+            assert!(
+                diagnostics
+                    .iter()
+                    .all(|diagnostic| diagnostic.code().as_deref() == Some("bus-word-exceeded"))
+            );
+        }
+
+        /// Render a bus-backed HAL with an interrupt.
+        ///
+        /// Expected behavior: No vector table or default handler linker script is generated.
+        #[test]
+        fn bus_interrupts() {
+            let hal = Hal::new([Peripheral::new("foo", 1, [Register::new("foo0", 1, [])])])
+                .interrupts([Interrupt::handler("FOO")])
+                .bus(Bus::new(Word::U8))
+                .validate()
+                .unwrap();
+
+            assert!(hal.device_x().is_none());
+            assert!(!hal.render().unwrap().contains("__INTERRUPTS"));
+        }
+
+        /// Render the interrupt vector table for the standalone runtime.
+        ///
+        /// Expected behavior: No cortex-m-rt items, NVIC masks, or default handler linker script
+        /// are generated.
+        #[test]
+        fn standalone_runtime() {
+            let hal = Hal::new([Peripheral::new("foo", 0, [])])
+                .interrupts([
+                    Interrupt::reserved(),
+                    Interrupt::handler("FOO").sources(["foo"]),
+                ])
+                .runtime(Runtime::Standalone)
+                .validate()
+                .unwrap();
+
+            let codegen = hal.render().unwrap();
+
+            assert!(!codegen.contains("cortex_m_rt"));
+            assert!(!codegen.contains(".vector_table.interrupts"));
+            assert!(!codegen.contains("pub mod interrupts"));
+            assert!(codegen.contains("__INTERRUPTS"));
+            assert!(hal.interrupts.device_x().is_none());
+        }
+
+        /// Render the external interrupt table for the riscv-rt runtime.
+        ///
+        /// Expected behavior: No cortex-m-rt items or NVIC masks are generated. The handlers are
+        /// re-exported from riscv-rt, the table is dispatched by position, and the interrupts are
+        /// numbered as external interrupts.
+        #[test]
+        fn riscv_runtime() {
+            let hal = Hal::new([Peripheral::new("foo", 0, [])])
+                .interrupts([
+                    Interrupt::reserved(),
+                    Interrupt::handler("FOO").sources(["foo"]),
+                ])
+                .runtime(Runtime::RiscvRt)
+                .validate()
+                .unwrap();
+
+            let codegen = hal.render().unwrap();
+
+            assert!(!codegen.contains("cortex_m_rt"));
+            assert!(!codegen.contains(".vector_table.interrupts"));
+            assert!(!codegen.contains("pub mod interrupts"));
+            assert!(codegen.contains("pub use ::riscv_rt::external_interrupt as interrupt;"));
+            assert!(codegen.contains("pub static __EXTERNAL_INTERRUPTS"));
+            assert!(codegen.contains("pub unsafe extern \"C\" fn _dispatch_external_interrupt"));
+            assert!(codegen.contains("const MAX_INTERRUPT_NUMBER: usize = 1usize;"));
+            assert!(codegen.contains("1 => Ok(Self::FOO)"));
+            assert!(codegen.contains("unsafe impl ::riscv::ExternalInterruptNumber for interrupt"));
+            assert!(hal.interrupts.device_x().is_some());
+        }
+
+        /// Declare core metadata which cannot be targeted.
+        ///
+        /// Expected behavior: The out of range priority bits and the bus-backed HAL are both
+        /// reported, while valid metadata renders the device contract.
+        #[test]
+        fn cpu() {
+            let diagnostics = Hal::new([])
+                .cpu(Cpu::new(Core::M4, 9))
+                .bus(Bus::new(Word::U8))
+                .validate()
+                .unwrap_err();
+
+            let mut codes = diagnostics
+                .iter()
+                .filter_map(|diagnostic| diagnostic.code().as_deref())
+                .collect::<Vec<_>>();
+            codes.sort();
+
+            assert_eq!(codes, ["cpu-unsupported", "invalid-priority-bits"]);
+
+            let codegen = Hal::new([])
+                .interrupts([Interrupt::handler("FOO")])
+                .cpu(Cpu::new(Core::M4, 4).fpu())
+                .validate()
+                .unwrap()
+                .render()
+                .unwrap();
+
+            assert!(codegen.contains("NVIC_PRIO_BITS: u8 = 4"));
+            assert!(codegen.contains("InterruptNumber for interrupt"));
+        }
+
+        /// Create interrupt tables which cannot be linked against the core.
+        ///
+        /// Expected behavior: A keyword handler, a handler colliding with a core exception,
+        /// and a table longer than the core supports are all reported.
+        #[test]
+        fn interrupt_table_limits() {
+            let diagnostics = Hal::new([])
+                .interrupts([Interrupt::handler("type"), Interrupt::handler("SysTick")])
+                .validate()
+                .unwrap_err();
+
+            let mut codes = diagnostics
+                .iter()
+                .filter_map(|diagnostic| diagnostic.code().as_deref())
+                .collect::<Vec<_>>();
+            codes.sort();
+
+            assert_eq!(codes, ["ident-collision", "invalid-ident"]);
+
+            let diagnostics = Hal::new([])
+                .interrupts((0..33).map(|i| Interrupt::handler(format!("IRQ{i}"))))
+                .cpu(Cpu::new(Core::M0, 2))
+                .validate()
+                .unwrap_err();
+
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(
+                diagnostics.iter().next().unwrap().code().as_deref(),
+                Some("interrupt-table-overflow")
+            );
+        }
+
+        /// Verify an interrupt table against the interrupt numbers of an SVD.
+        ///
+        /// Expected behavior: The misnumbered, misspelled, and missing interrupts are reported,
+        /// while the correctly numbered interrupt is not.
+        #[test]
+        fn interrupt_map() {
+            let expected = proto_hal_build::svd::interrupts(
+                r#"
+                <device>
+                    <peripheral>
+                        <interrupt><name>WWDG</name><value>0</value></interrupt>
+                        <interrupt><name>PVD</name><value>1</value></interrupt>
+                        <interrupt><name>DMA1_CH1</name><value>0x2</value></interrupt>
+                        <interrupt><name>DMA1_CH2</name><value>3</value></interrupt>
+                    </peripheral>
+                </device>
+                "#,
+            );
+
+            assert_eq!(expected.len(), 4);
+
+            let hal = Hal::new([]).interrupts([
+                Interrupt::handler("WWDG"),
+                Interrupt::handler("DAM1_CH1"),
+                Interrupt::reserved(),
+                Interrupt::handler("DMA1_CH2"),
+                Interrupt::handler("PVD"),
+            ]);
+
+            let diagnostics = hal.interrupts.verify(&expected);
+
+            let mut codes = diagnostics
+                .iter()
+                .filter_map(|diagnostic| diagnostic.code().as_deref())
+                .collect::<Vec<_>>();
+            codes.sort();
+
+            assert_eq!(
+                codes,
+                [
+                    "interrupt-missing",
+                    "interrupt-position-mismatch",
+                    "interrupt-unexpected"
+                ]
+            );
+        }
+
+        /// Describe a memory map with overlapping regions and a peripheral within RAM.
+        ///
+        /// Expected behavior: The overlap and the misplaced peripheral are both reported.
+        #[test]
+        fn memory_layout() {
+            let diagnostics = Hal::new([Peripheral::new(
+                "foo",
+                0x2000_0100,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new("a", 0, 8, Access::read(Numericity::Numeric))],
+                )],
+            )])
+            .memory([
+                Region::flash("flash", 0x0800_0000, 0x1_0000),
+                Region::ram("ram", 0x2000_0000, 0x1000),
+                Region::ram("ram2", 0x2000_0800, 0x1000),
+            ])
+            .validate()
+            .unwrap_err();
+
+            let mut codes = diagnostics
+                .iter()
+                .filter_map(|diagnostic| diagnostic.code().as_deref())
+                .collect::<Vec<_>>();
+            codes.sort();
+
+            assert_eq!(codes, ["memory-overlap", "peripheral-in-memory"]);
+        }
+
+        /// Describe a memory map with a region which contains one region and overlaps another
+        /// which is not its neighbour.
+        ///
+        /// Expected behavior: Both overlaps of the enclosing region are reported.
+        #[test]
+        fn memory_overlap_nested() {
+            let diagnostics = Hal::new([])
+                .memory([
+                    Region::ram("ram", 0x2000_0000, 0x1000),
+                    Region::ram("ram2", 0x2000_0100, 0x100),
+                    Region::ram("ram3", 0x2000_0800, 0x1000),
+                ])
+                .validate()
+                .unwrap_err();
+
+            assert_eq!(
+                diagnostics
+                    .iter()
+                    .filter(|diagnostic| diagnostic.code().as_deref() == Some("memory-overlap"))
+                    .count(),
+                2
+            );
+        }
+
+        /// Render the memory map of a HAL.
+        ///
+        /// Expected behavior: The linker script declares every region with its attributes, and
+        /// a descriptor is generated for every region.
+        #[test]
+        fn memory_x() {
+            let hal = Hal::new([])
+                .memory([
+                    Region::flash("flash", 0x0800_0000, 128 * 1024),
+                    Region::ram("ram", 0x2000_0000, 0x5800),
+                    Region::read_only("option_bytes", 0x1fff_7800, 0x30),
+                ])
+                .validate()
+                .unwrap();
+
+            assert_eq!(
+                hal.memory_x().unwrap(),
+                "MEMORY\n{\n  \
+                 FLASH (rx) : ORIGIN = 0x08000000, LENGTH = 128K\n  \
+                 RAM (rwx) : ORIGIN = 0x20000000, LENGTH = 22K\n  \
+                 OPTION_BYTES (r) : ORIGIN = 0x1fff7800, LENGTH = 48\n\
+                 }\n"
+            );
+
+            let codegen = hal.render().unwrap();
+
+            assert!(codegen.contains("pub const OPTION_BYTES: ::proto_hal::memory::Region"));
+        }
+
+        /// Render a HAL with an asynchronous bus.
+        ///
+        /// Expected behavior: The generated code is syntactically valid.
+        #[test]
+        fn bus_asynchronous_render() {
+            let hal = Hal::new([Peripheral::new(
+                "foo",
+                0,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new(
+                        "a",
+                        0,
+                        8,
+                        Access::read_write(Numericity::Numeric),
+                    )],
+                )
+                .reset(0)],
+            )])
+            .bus(
+                Bus::new(Word::U16)
+                    .endianness(Endianness::Little)
+                    .asynchronous(),
+            );
+
+            assert!(hal.validate().unwrap().render().is_ok());
+        }
     }
 
     mod peripherals {
         use proto_hal_build::ir::{
-            structures::{peripheral::Peripheral, register::Register},
+            access::Access,
+            structures::{
+                entitlement::Entitlement,
+                field::{Field, Numericity},
+                peripheral::Peripheral,
+                register::Register,
+                variant::Variant,
+            },
             utils::diagnostic::{self, Context},
         };
 
@@ -229,5 +766,697 @@ mod tests {
             // TODO: match exact diagnostic kind
             assert!(diagnostics.next().is_none());
         }
+
+        /// Declare a reset line for a peripheral with a resolvable field which is entitled.
+        ///
+        /// Expected behavior: Exactly one diagnostic error is emitted during validation.
+        #[test]
+        fn reset_line_entitled() {
+            let peripheral = Peripheral::new(
+                "foo",
+                0,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new(
+                        "a",
+                        0,
+                        1,
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new("A", 0),
+                            Variant::new("B", 1),
+                        ])),
+                    )
+                    .entitlements([Entitlement::to("bar::bar0::b::B")])],
+                )
+                .reset(0)],
+            )
+            .reset_line(Entitlement::to("bar::bar0::rst::Asserted"));
+
+            let mut diagnostics = peripheral.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert!(matches!(diagnostic.kind(), diagnostic::Kind::Error));
+            assert_eq!(diagnostic.code().as_deref(), Some("reset-line-entitled"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Declare a clock gate for a peripheral with a resolvable field which is entitled.
+        ///
+        /// Expected behavior: Exactly one diagnostic error is emitted during validation.
+        #[test]
+        fn clock_gate_entitled() {
+            let peripheral = Peripheral::new(
+                "foo",
+                0,
+                [Register::new(
+                    "foo0",
+                    0,
+                    [Field::new(
+                        "a",
+                        0,
+                        1,
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new("A", 0),
+                            Variant::new("B", 1),
+                        ])),
+                    )
+                    .entitlements([Entitlement::to("bar::bar0::b::B")])],
+                )
+                .reset(0)],
+            )
+            .clock_enable(Entitlement::to("bar::bar0::en::Enabled"))
+            .clock_gate(Entitlement::to("bar::bar0::en::Disabled"));
+
+            let mut diagnostics = peripheral.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert!(matches!(diagnostic.kind(), diagnostic::Kind::Error));
+            assert_eq!(diagnostic.code().as_deref(), Some("clock-gate-entitled"));
+            assert!(diagnostics.next().is_none());
+        }
+    }
+
+    mod registers {
+        use proto_hal_build::ir::{
+            access::Access,
+            structures::{
+                field::{Field, Numericity},
+                register::Register,
+                variant::Variant,
+            },
+            utils::diagnostic::{self, Context},
+        };
+
+        /// Create a register whose reset value does not correspond to any variant of a
+        /// resolvable field.
+        ///
+        /// Expected behavior: Exactly one diagnostic error is emitted during validation.
+        #[test]
+        fn reset_unmatched() {
+            let register = Register::new(
+                "foo",
+                0,
+                [Field::new(
+                    "a",
+                    0,
+                    2,
+                    Access::read_write(Numericity::enumerated([
+                        Variant::new("A", 0),
+                        Variant::new("B", 1),
+                    ])),
+                )],
+            )
+            .reset(2);
+
+            let mut diagnostics = register.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert!(matches!(diagnostic.kind(), diagnostic::Kind::Error));
+            assert_eq!(diagnostic.code().as_deref(), Some("reset-unmatched"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create a register containing a field of zero width.
+        ///
+        /// Expected behavior: Exactly one diagnostic error is emitted during validation.
+        #[test]
+        fn zero_width_field() {
+            let register = Register::new(
+                "foo",
+                0,
+                [Field::new(
+                    "a",
+                    0,
+                    0,
+                    Access::read_write(Numericity::Numeric),
+                )],
+            )
+            .reset(0);
+
+            let mut diagnostics = register.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("field-zero-width"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create a shadowed register containing a readable field.
+        ///
+        /// Expected behavior: Exactly one `shadow-readable` error is emitted during validation.
+        #[test]
+        fn shadowed_readable() {
+            let register = Register::new(
+                "foo",
+                0,
+                [
+                    Field::new("a", 0, 1, Access::write(Numericity::Numeric)),
+                    Field::new("b", 1, 1, Access::read(Numericity::Numeric)),
+                ],
+            )
+            .reset(0)
+            .shadowed();
+
+            let mut diagnostics = register.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("shadow-readable"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create a register containing a field whose identifier is a Rust keyword.
+        ///
+        /// Expected behavior: Exactly one diagnostic error is emitted during validation,
+        /// suggesting an alternative identifier.
+        #[test]
+        fn keyword_field() {
+            let register = Register::new(
+                "foo",
+                0,
+                [Field::new(
+                    "type",
+                    0,
+                    1,
+                    Access::read_write(Numericity::Numeric),
+                )],
+            )
+            .reset(0);
+
+            let mut diagnostics = register.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("invalid-ident"));
+            assert!(
+                diagnostic
+                    .oneline()
+                    .contains("consider renaming [type] to [type_]")
+            );
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create a register containing two distinct fields whose type names are equal.
+        ///
+        /// Expected behavior: Exactly two diagnostic errors are emitted during validation
+        /// (one for the field type names and one for the refined writer type names).
+        #[test]
+        fn field_type_name_collision() {
+            let register = Register::new(
+                "foo",
+                0,
+                [
+                    Field::new("foo_1", 0, 1, Access::read_write(Numericity::Numeric)),
+                    Field::new("foo1", 1, 1, Access::read_write(Numericity::Numeric)),
+                ],
+            )
+            .reset(0);
+
+            let diagnostics = register.validate(&Context::new());
+
+            assert_eq!(diagnostics.len(), 2);
+            assert!(
+                diagnostics
+                    .iter()
+                    .all(|diagnostic| diagnostic.code().as_deref() == Some("ident-collision"))
+            );
+        }
+
+        /// Create a register containing a field whose type name collides with a generated item.
+        ///
+        /// Expected behavior: Exactly one diagnostic error is emitted during validation.
+        #[test]
+        fn field_generated_item_collision() {
+            let register = Register::new(
+                "foo",
+                0,
+                [Field::new(
+                    "reader",
+                    0,
+                    1,
+                    Access::read_write(Numericity::Numeric),
+                )],
+            )
+            .reset(0);
+
+            let mut diagnostics = register.validate(&Context::new()).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("ident-collision"));
+            assert!(diagnostics.next().is_none());
+        }
+    }
+
+    mod lints {
+        use proto_hal_build::ir::{
+            access::Access,
+            lint::{Lint, Lints},
+            structures::{
+                entitlement::Entitlement,
+                field::{Field, Numericity},
+                hal::Hal,
+                interrupts::Interrupt,
+                peripheral::Peripheral,
+                register::Register,
+                variant::Variant,
+            },
+            utils::diagnostic,
+        };
+
+        fn hal(fields: impl IntoIterator<Item = Field>, reset: u32) -> Hal {
+            Hal::new([Peripheral::new(
+                "foo",
+                0,
+                [Register::new("foo0", 0, fields).reset(reset)],
+            )])
+        }
+
+        /// Create a resolvable field whose variants leave bit patterns unassigned.
+        ///
+        /// Expected behavior: Exactly one diagnostic warning is emitted for the field.
+        #[test]
+        fn variant_gaps() {
+            let hal = hal(
+                [Field::new(
+                    "a",
+                    0,
+                    2,
+                    Access::read_write(Numericity::enumerated([
+                        Variant::new("A", 0),
+                        Variant::new("B", 1),
+                    ])),
+                )],
+                0,
+            )
+            .validate()
+            .unwrap();
+
+            let mut diagnostics = hal.lint(&Lints::from([Lint::VariantGaps])).into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert!(matches!(diagnostic.kind(), diagnostic::Kind::Warning));
+            assert_eq!(diagnostic.code().as_deref(), Some("variant-gaps"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create an interrupt with a source peripheral, one without, and a reserved interrupt.
+        ///
+        /// Expected behavior: Exactly one diagnostic warning is emitted for the interrupt without
+        /// a source.
+        #[test]
+        fn unassociated_interrupt() {
+            let hal = hal([], 0)
+                .interrupts([
+                    Interrupt::handler("A").sources(["foo"]),
+                    Interrupt::reserved(),
+                    Interrupt::handler("B"),
+                ])
+                .validate()
+                .unwrap();
+
+            let mut diagnostics = hal
+                .lint(&Lints::from([Lint::UnassociatedInterrupt]))
+                .into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert!(matches!(diagnostic.kind(), diagnostic::Kind::Warning));
+            assert_eq!(diagnostic.code().as_deref(), Some("unassociated-interrupt"));
+            assert_eq!(
+                diagnostic
+                    .context()
+                    .as_ref()
+                    .map(|context| context.path().clone()),
+                Some(vec!["interrupts".to_string(), "B".to_string()])
+            );
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create an interrupt table with a long interior run and a trailing run of reserved
+        /// interrupts.
+        ///
+        /// Expected behavior: Both runs are reported, while the short run is not.
+        #[test]
+        fn reserved_runs() {
+            let hal = Hal::new([])
+                .interrupts(
+                    [Interrupt::handler("A"), Interrupt::reserved()]
+                        .into_iter()
+                        .chain([Interrupt::handler("B")])
+                        .chain((0..16).map(|_| Interrupt::reserved()))
+                        .chain([Interrupt::handler("C"), Interrupt::reserved()]),
+                )
+                .validate()
+                .unwrap();
+
+            let diagnostics = hal.lint(&Lints::from([Lint::ReservedRuns]));
+
+            assert_eq!(diagnostics.len(), 2);
+            assert!(diagnostics.iter().all(|diagnostic| {
+                matches!(diagnostic.kind(), diagnostic::Kind::Warning)
+                    && diagnostic.code().as_deref() == Some("reserved-runs")
+            }));
+        }
+
+        /// Create a resolvable field which no entitlement references.
+        ///
+        /// Expected behavior: Exactly one diagnostic warning is emitted for the field.
+        #[test]
+        fn unreferenced_state() {
+            let hal = hal(
+                [Field::new(
+                    "a",
+                    0,
+                    1,
+                    Access::read_write(Numericity::enumerated([
+                        Variant::new("A", 0),
+                        Variant::new("B", 1),
+                    ])),
+                )],
+                0,
+            )
+            .validate()
+            .unwrap();
+
+            let mut diagnostics = hal
+                .lint(&Lints::from([Lint::UnreferencedState]))
+                .into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("unreferenced-state"));
+            assert!(diagnostics.next().is_none());
+        }
+
+        /// Create two fields whose non-reset variants are entitled to each other.
+        ///
+        /// Expected behavior: Both non-reset variants are reported as unreachable.
+        #[test]
+        fn unreachable_variant() {
+            let hal = hal(
+                [
+                    Field::new(
+                        "a",
+                        0,
+                        1,
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new("A", 0),
+                            Variant::new("B", 1).entitlements([Entitlement::to("foo::foo0::b::Y")]),
+                        ])),
+                    ),
+                    Field::new(
+                        "b",
+                        1,
+                        1,
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new("X", 0),
+                            Variant::new("Y", 1).entitlements([Entitlement::to("foo::foo0::a::B")]),
+                        ])),
+                    ),
+                ],
+                0,
+            )
+            .validate()
+            .unwrap();
+
+            let diagnostics = hal.lint(&Lints::from([Lint::UnreachableVariant]));
+
+            assert_eq!(diagnostics.len(), 2);
+            assert!(
+                diagnostics
+                    .iter()
+                    .all(|diagnostic| diagnostic.code().as_deref() == Some("unreachable-variant"))
+            );
+        }
+
+        /// Create a register whose reset value sets bits outside of any field.
+        ///
+        /// Expected behavior: Exactly one diagnostic warning is emitted for the register.
+        #[test]
+        fn uncovered_reset_bits() {
+            let hal = hal(
+                [Field::new(
+                    "a",
+                    0,
+                    1,
+                    Access::read_write(Numericity::Numeric),
+                )],
+                0b10,
+            )
+            .validate()
+            .unwrap();
+
+            let mut diagnostics = hal
+                .lint(&Lints::from([Lint::UncoveredResetBits]))
+                .into_iter();
+
+            let diagnostic = diagnostics.next().unwrap();
+
+            assert_eq!(diagnostic.code().as_deref(), Some("uncovered-reset-bits"));
+            assert!(diagnostics.next().is_none());
+        }
+    }
+
+    mod diagnostics {
+        use proto_hal_build::ir::{
+            access::Access,
+            structures::{
+                field::{Field, Numericity},
+                hal::Hal,
+                peripheral::Peripheral,
+                register::Register,
+                variant::Variant,
+            },
+            utils::diagnostic::{Context, Diagnostic},
+        };
+
+        /// Render the diagnostics of an erroneous HAL as JSON.
+        ///
+        /// Expected behavior: The JSON contains one entry with the kind, code, context path,
+        /// message, and notes of the diagnostic.
+        #[test]
+        fn json() {
+            let hal = Hal::new([
+                Peripheral::new("foo", 0, [Register::new("foo0", 0, [])]),
+                Peripheral::new("bar", 0, [Register::new("bar0", 0, [])]),
+            ]);
+
+            let json = Diagnostic::report_json(&hal.validate().unwrap_err());
+            let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+            let entries = value.as_array().unwrap();
+
+            assert_eq!(entries.len(), 1);
+            assert_eq!(entries[0]["kind"], "error");
+            assert_eq!(entries[0]["code"], "peripheral-overlap");
+            assert_eq!(entries[0]["context"], serde_json::json!([]));
+            assert!(entries[0]["message"].as_str().unwrap().contains("overlap"));
+            assert_eq!(entries[0]["notes"], serde_json::json!([]));
+        }
+
+        /// Render a diagnostic on a single line.
+        ///
+        /// Expected behavior: The location, code, context path, message, and notes all appear on
+        /// one line.
+        #[test]
+        fn oneline() {
+            let peripheral = Peripheral::new(
+                "foo",
+                0,
+                [Register::new("foo", 0, []), Register::new("bar", 0, [])],
+            );
+
+            let diagnostics = peripheral.validate(&Context::new());
+            let line = diagnostics.iter().next().unwrap().oneline();
+
+            assert!(!line.contains('\n'));
+            assert!(line.contains("lib.rs:"));
+            assert!(line.contains(": [register-overlap] in foo: "));
+        }
+
+        /// Report the model location of an erroneous definition.
+        ///
+        /// Expected behavior: The diagnostic points at the line which constructed the variant.
+        #[test]
+        fn location() {
+            let variant = Variant::new("variant", 0);
+            let line = line!() - 1;
+
+            let field = Field::new(
+                "foo",
+                0,
+                1,
+                Access::read_write(Numericity::enumerated([variant])),
+            );
+
+            let diagnostics = field.validate(&Context::new());
+            let location = diagnostics
+                .iter()
+                .next()
+                .unwrap()
+                .location()
+                .clone()
+                .unwrap();
+
+            assert!(location.file().ends_with("lib.rs"));
+            assert_eq!(*location.line(), line);
+        }
+    }
+
+    mod generate {
+        use std::{collections::HashMap, env, fs, path::PathBuf, sync::Mutex};
+
+        use proto_hal_build::{
+            codegen::{self, Config},
+            ir::{
+                access::Access,
+                lint::Lint,
+                structures::{
+                    entitlement::Entitlement,
+                    field::{Field, Numericity},
+                    hal::Hal,
+                    interrupts::Interrupt,
+                    peripheral::Peripheral,
+                    register::Register,
+                    variant::Variant,
+                },
+            },
+        };
+
+        /// Generate the HAL into a fresh output directory, returning the directory.
+        fn generate(name: &str, source: impl FnOnce() -> Hal, config: Config) -> PathBuf {
+            // `OUT_DIR` is shared by the whole process, so only one HAL is generated at a time
+            static OUT_DIR: Mutex<()> = Mutex::new(());
+            let _guard = OUT_DIR.lock().unwrap();
+
+            let out_dir = env::temp_dir().join(format!("proto-hal-generate-{name}"));
+            let _ = fs::remove_dir_all(&out_dir);
+            fs::create_dir_all(&out_dir).unwrap();
+
+            unsafe { env::set_var("OUT_DIR", &out_dir) };
+            codegen::generate_with(source, config);
+
+            out_dir
+        }
+
+        /// Generate a HAL which violates lints with every lint enabled.
+        ///
+        /// Expected behavior: The lints are reported, but the HAL is still generated.
+        #[test]
+        fn lints() {
+            let out_dir = generate(
+                "lints",
+                || Hal::new([Peripheral::new("foo", 0, [])]),
+                Config::new().lints(Lint::all()),
+            );
+
+            let json = fs::read_to_string(out_dir.join("diagnostics.json")).unwrap();
+            let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+            assert!(
+                value
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|entry| entry["code"] == "missing-docs")
+            );
+            assert!(out_dir.join("hal.rs").exists());
+        }
+
+        /// Generate a HAL which reserves an expected interrupt, verified against a partial
+        /// interrupt map.
+        ///
+        /// Expected behavior: The missing interrupt is reported, but the HAL is still generated.
+        #[test]
+        fn interrupt_map_partial() {
+            let out_dir = generate(
+                "interrupt-map-partial",
+                || {
+                    Hal::new([Peripheral::new("foo", 0, [])])
+                        .interrupts([Interrupt::handler("WWDG"), Interrupt::reserved()])
+                },
+                Config::new().interrupt_map(HashMap::from([
+                    ("WWDG".to_string(), 0),
+                    ("PVD".to_string(), 1),
+                ])),
+            );
+
+            let json = fs::read_to_string(out_dir.join("diagnostics.json")).unwrap();
+            let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+            assert!(
+                value
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|entry| entry["code"] == "interrupt-missing")
+            );
+            assert!(out_dir.join("hal.rs").exists());
+        }
+
+        /// Generate a HAL with a reset line for a peripheral with a resolvable field which is
+        /// entitled.
+        ///
+        /// Expected behavior: The error is reported and the HAL is not generated.
+        #[test]
+        fn reset_line_entitled() {
+            let out_dir = generate(
+                "reset-line-entitled",
+                || {
+                    let enumerated = |a, b| {
+                        Access::read_write(Numericity::enumerated([
+                            Variant::new(a, 0),
+                            Variant::new(b, 1),
+                        ]))
+                    };
+
+                    Hal::new([
+                        Peripheral::new(
+                            "foo",
+                            0,
+                            [Register::new(
+                                "foo0",
+                                0,
+                                [Field::new("a", 0, 1, enumerated("A", "B"))
+                                    .entitlements([Entitlement::to("bar::bar0::b::B")])],
+                            )
+                            .reset(0)],
+                        )
+                        .reset_line(Entitlement::to("bar::bar0::rst::Asserted")),
+                        Peripheral::new(
+                            "bar",
+                            0x100,
+                            [Register::new(
+                                "bar0",
+                                0,
+                                [
+                                    Field::new("b", 0, 1, enumerated("A", "B")),
+                                    Field::new("rst", 1, 1, enumerated("Released", "Asserted")),
+                                ],
+                            )
+                            .reset(0)],
+                        ),
+                    ])
+                },
+                Config::new(),
+            );
+
+            let json = fs::read_to_string(out_dir.join("diagnostics.json")).unwrap();
+            let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+
+            assert!(
+                value
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .any(|entry| entry["code"] == "reset-line-entitled")
+            );
+            assert!(!out_dir.join("hal.rs").exists());
+        }
     }
 }
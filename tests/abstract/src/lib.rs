@@ -16,6 +16,26 @@ mod tests {
                 p.foo.foo0.a.type_id(),
             );
         }
+
+        /// Take the peripherals through the singleton guard.
+        ///
+        /// Expected behavior: The peripherals are undeclared until they are declared to be in the
+        /// reset state, and are only taken once.
+        #[test]
+        fn take() {
+            assert!(matches!(
+                crate::Peripherals::take(),
+                Err(proto_hal::TakeError::Undeclared)
+            ));
+
+            unsafe { crate::Peripherals::declare_reset_on_startup() };
+
+            assert!(crate::Peripherals::take().is_ok());
+            assert!(matches!(
+                crate::Peripherals::take(),
+                Err(proto_hal::TakeError::Taken)
+            ));
+        }
     }
 
     mod peripherals {
@@ -35,22 +55,15 @@ mod tests {
             extern crate std;
             use crate::foo::{self, foo0};
 
-            static mut MOCK_FOO: u32 = u32::MAX;
-
-            #[unsafe(export_name = "__PROTO_HAL_ADDR_OF_FOO")]
-            fn addr_of() -> usize {
-                (&raw const MOCK_FOO).addr()
-            }
-
             #[test]
             fn harness_addressing() {
-                assert_eq!(foo::base_addr(), addr_of());
+                assert_eq!(foo::base_addr(), (&raw const foo::MOCK).addr());
             }
 
             #[test]
             fn unsafe_read() {
                 critical_section::with(|_| {
-                    unsafe { MOCK_FOO = foo0::a::Variant::V1 as _ };
+                    unsafe { foo::MOCK[0] = foo0::a::Variant::V1 as _ };
                     assert!(unsafe { foo0::read_untracked().a().is_v1() });
                 });
             }
@@ -105,4 +118,516 @@ mod tests {
             foo::foo1::read().read_requires_v5(&mut p.foo.foo1.read_requires_v5, &a);
         }
     }
+
+    mod sim {
+        use proto_hal::sim::WriteEvent;
+
+        use crate::foo::{foo0, foo1};
+
+        #[test]
+        fn reset_values() {
+            let _sim = crate::simulator().install();
+
+            assert!(unsafe { foo0::read_untracked().a().is_v3() });
+        }
+
+        #[test]
+        fn access() {
+            let sim = crate::simulator().install();
+
+            // only the writable field is affected by writes
+            unsafe {
+                foo1::write_from_zero_untracked(|w| {
+                    w.set_bits(u32::MAX);
+                    w
+                })
+            };
+            assert_eq!(sim.with(|sim| sim.peek("foo", "foo1")), 0b01);
+
+            // only the readable field is visible to reads
+            sim.with(|sim| sim.poke("foo", "foo1", 0b11));
+            assert_eq!(unsafe { foo1::read_untracked().bits() }, 0b10);
+        }
+
+        #[test]
+        fn hooks() {
+            let sim = crate::simulator()
+                .on_read("foo", "foo0", |value| *value = foo0::a::Variant::V5 as _)
+                .on_write("foo", "foo0", |value, WriteEvent { previous, .. }| {
+                    // emulate a register which ignores writes
+                    *value = previous
+                })
+                .install();
+
+            unsafe { foo0::write_from_zero_untracked(|w| w.a(foo0::a::WriteVariant::V1)) };
+            assert_eq!(sim.with(|sim| sim.field("foo", "foo0", "a")), 3);
+
+            assert!(unsafe { foo0::read_untracked().a().is_v5() });
+        }
+    }
+
+    mod shadow {
+        use proto_hal::trace::{Kind, Recording};
+
+        use crate::baz::{self, baz0};
+
+        /// Modify a shadowed write-only register through every write path.
+        ///
+        /// Expected behavior: The shadow tracks every write, modifications merge into the
+        /// shadow, and the register is never read.
+        #[test]
+        fn modify() {
+            let recording = Recording::start();
+
+            let mut p = unsafe { crate::peripherals() };
+
+            assert_eq!(baz0::shadow(), 0x2001);
+
+            baz0::write(|w| {
+                w.mode(&mut p.baz.baz0.mode)
+                    .m2()
+                    .level(&mut p.baz.baz0.level, 0x12u32)
+            });
+
+            assert_eq!(baz0::shadow(), 0x1202);
+
+            baz0::modify(|w| w.mode(&mut p.baz.baz0.mode).m0());
+
+            assert_eq!(baz0::shadow(), 0x1200);
+            assert_eq!(unsafe { baz::MOCK[0] }, 0x1200);
+
+            critical_section::with(|cs| {
+                unsafe { baz0::write_from_zero_untracked(|w| w.level(0x34u32)) };
+                assert_eq!(baz0::shadow(), 0x3400);
+
+                unsafe { baz0::modify_untracked(cs, |w| w.mode(baz0::mode::WriteVariant::M1)) };
+            });
+
+            assert_eq!(baz0::shadow(), 0x3401);
+            assert_eq!(unsafe { baz::MOCK[0] }, 0x3401);
+
+            let trace = recording.finish();
+
+            assert!(trace.position("baz", "baz0", Kind::Read).is_none());
+            assert_eq!(trace.of("baz", "baz0").count(), 4);
+        }
+    }
+
+    mod wait {
+        extern crate std;
+
+        use core::{
+            pin::pin,
+            sync::atomic::{AtomicBool, Ordering},
+            task::{Context, Poll},
+        };
+        use std::{sync::Arc, task::Wake};
+
+        use proto_hal::wait::Timeout;
+
+        use crate::baz::baz1::status;
+
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        /// Block until a status field is set by the hardware.
+        ///
+        /// Expected behavior: The wait times out after `limit + 1` reads if the hardware never
+        /// writes the variant, and otherwise returns once it does.
+        #[test]
+        fn blocking() {
+            // the hardware sets the status on the fifth read
+            let mut reads = 0;
+            let _sim = crate::simulator()
+                .on_read("baz", "baz1", move |value| {
+                    reads += 1;
+
+                    if reads == 5 {
+                        *value = 1;
+                    }
+                })
+                .install();
+
+            let mut p = unsafe { crate::peripherals() };
+
+            assert_eq!(
+                status::wait_until_ready(&mut p.baz.baz1.status, Some(3)),
+                Err(Timeout)
+            );
+            assert_eq!(
+                status::wait_until_ready(&mut p.baz.baz1.status, None),
+                Ok(())
+            );
+        }
+
+        /// Await a status field, waking the task from the interrupt.
+        ///
+        /// Expected behavior: The future is pending until the hardware writes the variant,
+        /// and the interrupt wakes the task.
+        #[test]
+        fn future() {
+            let sim = crate::simulator().install();
+
+            let mut p = unsafe { crate::peripherals() };
+
+            let flag = Arc::new(Flag(AtomicBool::new(false)));
+            let waker = flag.clone().into();
+            let mut cx = Context::from_waker(&waker);
+
+            let mut future = pin!(status::wait_until_ready_async(&mut p.baz.baz1.status));
+
+            assert!(future.as_mut().poll(&mut cx).is_pending());
+
+            sim.with(|sim| sim.set_field("baz", "baz1", "status", 1));
+            crate::wake::BAZ();
+
+            assert!(flag.0.load(Ordering::Relaxed));
+            assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(()));
+        }
+    }
+
+    mod resolve {
+        use crate::foo::foo0::{self, a};
+
+        /// Resolve a dynamic field state into the static state held by the hardware.
+        ///
+        /// Expected behavior: The state held by the field is recovered, and resolving into
+        /// any other state (or from a value which corresponds to no variant) returns the
+        /// dynamic state.
+        #[test]
+        fn dynamic() {
+            let sim = crate::simulator().install();
+
+            let p = unsafe { crate::peripherals() };
+
+            let a = p.foo.foo0.a.into_dynamic();
+
+            sim.with(|sim| sim.set_field("foo", "foo0", "a", 4));
+
+            let Err(a) = a.try_resolve::<a::V2>() else {
+                panic!("the field does not hold V2");
+            };
+            let Ok(a) = a.try_resolve::<a::V4>() else {
+                panic!("the field holds V4");
+            };
+
+            // the recovered state can be transitioned statically
+            let foo0::States { a, .. } = foo0::write(|w| w.a(a).v1());
+
+            let Ok(a::Resolved::V1(a)) = a.into_dynamic().resolve() else {
+                panic!("the field holds V1");
+            };
+
+            // values which do not correspond to any variant are not resolved
+            sim.with(|sim| sim.set_field("foo", "foo0", "a", 9));
+
+            assert!(a.into_dynamic().resolve().is_err());
+        }
+    }
+
+    mod adopt {
+        extern crate std;
+        use std::vec::Vec;
+
+        use proto_hal::adopt::Unmodeled;
+
+        use crate::foo::foo0::{self, a};
+
+        /// Adopt the peripherals from the live registers rather than the reset state.
+        ///
+        /// Expected behavior: Resolvable fields are resolved into the states they hold, and
+        /// fields holding values which do not correspond to any variant are reported and
+        /// adopted as dynamic.
+        #[test]
+        fn handoff() {
+            let sim = crate::simulator().install();
+
+            // left behind by a bootloader
+            sim.with(|sim| sim.set_field("foo", "foo0", "a", 4));
+
+            let mut unmodeled = Vec::new();
+            let p = unsafe { crate::adopt(&mut |report| unmodeled.push(report)) };
+
+            assert!(unmodeled.is_empty());
+
+            let Ok(a::Resolved::V4(a)) = p.foo.foo0.a else {
+                panic!("the field holds V4");
+            };
+
+            // the adopted state can be transitioned statically
+            foo0::write(|w| w.a(a).v1());
+
+            sim.with(|sim| sim.set_field("foo", "foo0", "a", 9));
+
+            let p = unsafe { crate::adopt(&mut |report| unmodeled.push(report)) };
+
+            assert!(p.foo.foo0.a.is_err());
+            assert_eq!(
+                unmodeled,
+                [Unmodeled {
+                    peripheral: "foo",
+                    register: "foo0",
+                    field: "a",
+                    bits: 9,
+                }]
+            );
+        }
+    }
+
+    mod reset {
+        use core::any::{Any, TypeId};
+
+        use proto_hal::IntoReset;
+
+        use crate::foo::{
+            foo0::{self, a},
+            foo2::{self, c},
+        };
+
+        /// Restore field states and adopted peripherals to reset.
+        ///
+        /// Expected behavior: The reset value is written (including to write-only registers) and
+        /// the reset states are returned.
+        #[test]
+        fn into_reset() {
+            let sim = crate::simulator().install();
+
+            let p = unsafe { crate::peripherals() };
+
+            let states = foo0::write(|w| w.a(p.foo.foo0.a).v1());
+            let foo0::States { a } = states.into_reset();
+
+            assert_eq!(a.type_id(), TypeId::of::<a::V3>());
+            assert!(unsafe { foo0::read_untracked().a().is_v3() });
+
+            sim.with(|sim| sim.set_field("foo", "foo0", "a", 5));
+            sim.with(|sim| sim.set_field("foo", "foo3", "e", 0));
+
+            let foo = unsafe { crate::adopt(&mut |_| {}) }.foo.into_reset();
+
+            assert_eq!(foo.foo0.a.type_id(), TypeId::of::<a::V3>());
+            assert!(unsafe { foo0::read_untracked().a().is_v3() });
+            assert_eq!(sim.with(|sim| sim.field("foo", "foo3", "e")), 0x2a);
+        }
+
+        /// Restore the states of the resolvable fields of a register to reset.
+        ///
+        /// Expected behavior: Only the fields whose states are restored are written, so the
+        /// values of the other fields are preserved.
+        #[test]
+        fn owned_fields() {
+            let sim = crate::simulator().install();
+
+            let mut p = unsafe { crate::peripherals() };
+
+            let states = foo2::write(|w| w.c(p.foo.foo2.c).off().d(&mut p.foo.foo2.d, 7u32));
+            let foo2::States { c } = states.into_reset();
+
+            assert_eq!(c.type_id(), TypeId::of::<c::On>());
+            assert_eq!(sim.with(|sim| sim.field("foo", "foo2", "c")), 1);
+            assert_eq!(sim.with(|sim| sim.field("foo", "foo2", "d")), 7);
+
+            // holding the states of all fields, the whole register is written
+            let foo2::Fields { .. } = foo2::Fields { c, d: p.foo.foo2.d }.into_reset();
+
+            assert_eq!(sim.with(|sim| sim.field("foo", "foo2", "d")), 5);
+        }
+    }
+
+    mod interrupts {
+        use proto_hal::{
+            interrupt::Binding,
+            stasis::{Conjure, Freeze},
+        };
+
+        use crate::{
+            baz::baz1,
+            foo::foo2,
+            interrupts,
+            qux::{self, qux0},
+        };
+
+        proto_hal::bind! {
+            BAZ in crate: (baz1::status::Dynamic, bool) => |(status, ready)| {
+                *ready = baz1::read().status(status).is_ready();
+            }
+        }
+
+        /// Unmask and mask an interrupt associated with a peripheral in the NVIC.
+        ///
+        /// Expected behavior: The NVIC tracks the mask state of the interrupt.
+        #[test]
+        fn unmask() {
+            let sim = crate::simulator().install();
+
+            let p = unsafe { crate::peripherals() };
+
+            let baz = unsafe { p.interrupts.BAZ.unmask() };
+            assert!(sim.with(|sim| sim.is_unmasked(interrupts::BAZ::POSITION)));
+
+            let (_baz,) = baz.mask();
+            assert!(!sim.with(|sim| sim.is_unmasked(interrupts::BAZ::POSITION)));
+        }
+
+        /// Unmask an interrupt raised by a conditional peripheral with an interrupt enable, then
+        /// mask it again.
+        ///
+        /// Expected behavior: Unmasking the interrupt requires the entitlements of the unmasked
+        /// peripheral and the enabled interrupt request, and masking it releases them.
+        #[test]
+        fn entitled() {
+            let sim = crate::simulator().install();
+
+            let p = unsafe { crate::peripherals() };
+
+            let (c, [qux_entitlement, interrupt_entitlement]) = p.foo.foo2.c.freeze();
+
+            let qux = p.qux.unmask(qux_entitlement);
+            let qux0::States { ie } = qux0::write(|w| w.ie(qux.qux0.ie).enabled());
+            let (ie, [ie_entitlement]) = ie.freeze();
+
+            let quxi = unsafe {
+                p.interrupts
+                    .QUX
+                    .unmask(interrupt_entitlement, ie_entitlement)
+            };
+            assert!(sim.with(|sim| sim.is_unmasked(interrupts::QUX::POSITION)));
+
+            let (_quxi, interrupt_entitlement, ie_entitlement) = quxi.mask();
+            assert!(!sim.with(|sim| sim.is_unmasked(interrupts::QUX::POSITION)));
+
+            let qux0::States { ie } =
+                qux0::write(|w| w.ie(ie.release([ie_entitlement])).disabled());
+            let (_qux, qux_entitlement) = qux::mask(qux.entitlements, qux0::Fields { ie });
+
+            let _: foo2::c::On = c.release([qux_entitlement, interrupt_entitlement]);
+        }
+
+        /// Bind a field handle to the handler of an interrupt, run the handler, then unbind it.
+        ///
+        /// Expected behavior: The handler reads the register with the bound handle, and
+        /// unbinding masks the interrupt and returns the resources.
+        #[test]
+        fn bind() {
+            let sim = crate::simulator().install();
+
+            let p = unsafe { crate::peripherals() };
+
+            let (baz, displaced) = unsafe {
+                p.interrupts
+                    .BAZ
+                    .bind(&BAZ::BINDING, (p.baz.baz1.status, false))
+            };
+            assert!(displaced.is_none());
+            assert!(sim.with(|sim| sim.is_unmasked(interrupts::BAZ::POSITION)));
+
+            sim.with(|sim| sim.set_field("baz", "baz1", "status", 1));
+            unsafe { BAZ::BAZ() };
+
+            let (_baz, resources) = baz.unbind(&BAZ::BINDING);
+            assert!(resources.is_some_and(|(_, ready)| ready));
+            assert!(!sim.with(|sim| sim.is_unmasked(interrupts::BAZ::POSITION)));
+        }
+
+        /// Bind resources to the handler of a masked interrupt which still holds resources.
+        ///
+        /// Expected behavior: The resources previously bound are returned rather than dropped.
+        #[test]
+        fn rebind() {
+            let _sim = crate::simulator().install();
+
+            let p = unsafe { crate::peripherals() };
+            let status = || unsafe { <baz1::status::Dynamic as Conjure>::conjure() };
+
+            // a binding of its own, as the handler binding is shared with the other tests
+            let binding = Binding::new();
+
+            assert!(binding.bind(&p.interrupts.BAZ, (status(), true)).is_none());
+
+            let (baz, displaced) = unsafe { p.interrupts.BAZ.bind(&binding, (status(), false)) };
+            assert!(displaced.is_some_and(|(_, ready)| ready));
+
+            let (_baz, resources) = baz.unbind(&binding);
+            assert!(resources.is_some_and(|(_, ready)| !ready));
+        }
+    }
+
+    mod trace {
+        use proto_hal::trace::{Access, Kind, Recording};
+
+        use crate::foo::{self, foo0};
+
+        #[test]
+        fn sequence() {
+            let _sim = crate::simulator().install();
+            let recording = Recording::start();
+
+            let mut p = unsafe { crate::peripherals() };
+
+            let foo::foo0::States { a, .. } = foo::foo0::write(|w| w.a(p.foo.foo0.a).v5());
+
+            foo::foo1::write(|w| {
+                w.write_requires_v5(&mut p.foo.foo1.write_requires_v5, &a)
+                    .noop()
+            });
+
+            let trace = recording.finish();
+
+            assert_eq!(trace.accesses().len(), 2);
+            assert!(
+                trace.position("foo", "foo0", Kind::Write).unwrap()
+                    < trace.position("foo", "foo1", Kind::Write).unwrap()
+            );
+            assert_eq!(trace.of("foo", "foo0").next().unwrap().value, 5);
+        }
+
+        #[test]
+        fn modify_in_cs() {
+            let _sim = crate::simulator().install();
+            let recording = Recording::start();
+
+            let p = unsafe { crate::peripherals() };
+
+            foo0::modify(|_, w| w.a(p.foo.foo0.a).v1());
+
+            assert_eq!(
+                recording.finish().accesses(),
+                [
+                    Access {
+                        peripheral: "foo",
+                        register: "foo0",
+                        kind: Kind::Read,
+                        value: 3,
+                        via_modify_in_cs: true,
+                    },
+                    Access {
+                        peripheral: "foo",
+                        register: "foo0",
+                        kind: Kind::Write,
+                        value: 1,
+                        via_modify_in_cs: true,
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn redundant() {
+            let _sim = crate::simulator().install();
+            let recording = Recording::start();
+
+            for _ in 0..2 {
+                unsafe { foo0::write_from_zero_untracked(|w| w.a(foo0::a::WriteVariant::V2)) };
+            }
+
+            let trace = recording.finish();
+
+            assert_eq!(trace.redundant(), [&trace.accesses()[1]]);
+        }
+    }
 }
@@ -0,0 +1,69 @@
+use proto_hal_build::ir::{
+    access::Access,
+    bus::{Bus, Endianness, Word},
+    structures::{
+        entitlement::Entitlement,
+        field::{Field, Numericity},
+        hal::Hal,
+        peripheral::Peripheral,
+        register::Register,
+        variant::Variant,
+    },
+};
+
+/// A sensor with 16-bit big endian registers accessed over a bus, and a filter which is
+/// available while the sensor is enabled.
+pub fn generate() -> Hal {
+    Hal::new([
+        Peripheral::new(
+            "sensor",
+            0x20,
+            [
+                Register::new(
+                    "ctrl",
+                    0,
+                    [
+                        Field::new(
+                            "en",
+                            0,
+                            1,
+                            Access::read_write(Numericity::enumerated([
+                                Variant::new("Disabled", 0),
+                                Variant::new("Enabled", 1),
+                            ])),
+                        ),
+                        Field::new("gain", 8, 8, Access::read_write(Numericity::Numeric)),
+                    ],
+                )
+                .reset(0),
+                Register::new(
+                    "sample",
+                    1,
+                    [Field::new(
+                        "value",
+                        0,
+                        12,
+                        Access::read(Numericity::Numeric),
+                    )],
+                ),
+            ],
+        ),
+        Peripheral::new(
+            "filter",
+            0x30,
+            [Register::new(
+                "coef",
+                0,
+                [Field::new(
+                    "value",
+                    0,
+                    16,
+                    Access::read_write(Numericity::Numeric),
+                )],
+            )
+            .reset(0x1000)],
+        )
+        .entitlements([Entitlement::to("sensor::ctrl::en::Enabled")]),
+    ])
+    .bus(Bus::new(Word::U16).endianness(Endianness::Big))
+}
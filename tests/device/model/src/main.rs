@@ -0,0 +1,3 @@
+fn main() {
+    proto_hal_build::codegen::validate(device_model::generate);
+}
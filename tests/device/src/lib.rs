@@ -0,0 +1,169 @@
+#![no_std]
+
+include!(concat!(env!("OUT_DIR"), "/hal.rs"));
+
+#[cfg(test)]
+mod tests {
+    use proto_hal::bus::Bus;
+
+    /// A device register file indexed by register address.
+    struct Device {
+        registers: [[u8; 2]; 0x40],
+        fail: bool,
+    }
+
+    impl Device {
+        fn new() -> Self {
+            Self {
+                registers: [[0; 2]; 0x40],
+                fail: false,
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Nack;
+
+    impl Bus for Device {
+        type Error = Nack;
+
+        fn read(&mut self, address: u32, buf: &mut [u8]) -> Result<(), Self::Error> {
+            if self.fail {
+                Err(Nack)?
+            }
+
+            buf.copy_from_slice(&self.registers[address as usize]);
+            Ok(())
+        }
+
+        fn write(&mut self, address: u32, buf: &[u8]) -> Result<(), Self::Error> {
+            if self.fail {
+                Err(Nack)?
+            }
+
+            self.registers[address as usize].copy_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    mod registers {
+        use super::{Device, Nack};
+        use crate::sensor::{ctrl, sample};
+
+        /// Read a register through the bus.
+        ///
+        /// Expected behavior: The register is read from the base address plus the register
+        /// offset and decoded with the device byte order.
+        #[test]
+        fn read() {
+            let mut device = Device::new();
+            device.registers[0x21] = [0x0a, 0xbc];
+
+            let mut p = unsafe { crate::peripherals() };
+
+            assert_eq!(
+                sample::read(&mut device)
+                    .unwrap()
+                    .value(&mut p.sensor.sample.value),
+                0xabc
+            );
+        }
+
+        /// Write a register through the bus.
+        ///
+        /// Expected behavior: The register is encoded with the device byte order.
+        #[test]
+        fn unsafe_write() {
+            let mut device = Device::new();
+
+            unsafe { ctrl::write_from_zero_untracked(&mut device, |w| w.gain(0x12u32)) }.unwrap();
+
+            assert_eq!(device.registers[0x20], [0x12, 0x00]);
+        }
+
+        /// Modify a register through the type-state interface.
+        ///
+        /// Expected behavior: Unmodified fields are preserved and the new state is returned.
+        #[test]
+        fn modify() {
+            let mut device = Device::new();
+            device.registers[0x20] = [0x34, 0x00];
+
+            let p = unsafe { crate::peripherals() };
+
+            let ctrl::States { en, .. } =
+                ctrl::modify(&mut device, |_, w| w.en(p.sensor.ctrl.en).enabled()).unwrap();
+
+            assert_eq!(device.registers[0x20], [0x34, 0x01]);
+
+            let _: ctrl::en::Enabled = en;
+        }
+
+        /// Access a register while the bus fails.
+        ///
+        /// Expected behavior: The bus error is returned.
+        #[test]
+        fn bus_error() {
+            let mut device = Device::new();
+            device.fail = true;
+
+            assert!(matches!(sample::read(&mut device), Err(Nack)));
+            assert_eq!(
+                unsafe { ctrl::write_from_zero_untracked(&mut device, |w| w) },
+                Err(Nack)
+            );
+        }
+    }
+
+    mod conditional {
+        use proto_hal::stasis::Freeze;
+
+        use super::{Device, Nack};
+        use crate::{filter, sensor::ctrl};
+
+        /// Unmask a conditional peripheral, then mask it with the states of its fields.
+        ///
+        /// Expected behavior: The reset values of the registers are written through the bus and
+        /// the entitlements are released.
+        #[test]
+        fn mask() {
+            let mut device = Device::new();
+
+            let p = unsafe { crate::peripherals() };
+
+            let ctrl::States { en, .. } =
+                ctrl::modify(&mut device, |_, w| w.en(p.sensor.ctrl.en).enabled()).unwrap();
+            let (en, [entitlement]) = en.freeze();
+
+            let filter = p.filter.unmask(entitlement);
+            let (_filter, entitlement) =
+                filter::mask(&mut device, filter.entitlements, filter.coef).unwrap();
+
+            assert_eq!(device.registers[0x30], [0x10, 0x00]);
+
+            let _: ctrl::en::Enabled = en.release([entitlement]);
+        }
+
+        /// Mask a conditional peripheral while the bus fails.
+        ///
+        /// Expected behavior: The bus error is returned.
+        #[test]
+        fn mask_bus_error() {
+            let mut device = Device::new();
+
+            let p = unsafe { crate::peripherals() };
+
+            let ctrl::States { en, .. } =
+                ctrl::modify(&mut device, |_, w| w.en(p.sensor.ctrl.en).enabled()).unwrap();
+
+            let filter = p.filter.unmask(en);
+
+            device.fail = true;
+
+            assert!(matches!(
+                filter::mask(&mut device, filter.entitlements, filter.coef),
+                Err(Nack)
+            ));
+        }
+    }
+}
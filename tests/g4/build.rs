@@ -1,4 +1,5 @@
 use model::DeviceVariant;
+use proto_hal_build::codegen::Config;
 
 fn main() -> Result<(), String> {
     let variant = if cfg!(feature = "g431") {
@@ -13,7 +14,11 @@ fn main() -> Result<(), String> {
         Err("device variant must be specified")?
     };
 
-    proto_hal_build::codegen::generate(|| model::generate(variant));
+    // the peripherals are in reset on startup, as the HAL is not used behind a bootloader
+    proto_hal_build::codegen::generate_with(
+        || model::generate(variant),
+        Config::new().reset_on_startup(),
+    );
 
     Ok(())
 }
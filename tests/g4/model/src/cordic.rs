@@ -10,5 +10,7 @@ pub fn generate() -> Peripheral {
         0x4002_0c00,
         [csr::generate(), wdata::generate(), rdata::generate()],
     )
-    .entitlements([Entitlement::to("rcc::ahb1enr::cordicen::Enabled")])
+    .clock_enable(Entitlement::to("rcc::ahb1enr::cordicen::Enabled"))
+    .clock_gate(Entitlement::to("rcc::ahb1enr::cordicen::Disabled"))
+    .reset_line(Entitlement::to("rcc::ahb1rstr::cordicrst::Asserted"))
 }
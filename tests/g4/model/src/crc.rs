@@ -10,5 +10,7 @@ pub fn generate() -> Peripheral {
         0x4002_3000,
         [dr::generate(), idr::generate(), cr::generate()],
     )
-    .entitlements([Entitlement::to("rcc::ahb1enr::crcen::Enabled")])
+    .clock_enable(Entitlement::to("rcc::ahb1enr::crcen::Enabled"))
+    .clock_gate(Entitlement::to("rcc::ahb1enr::crcen::Disabled"))
+    .reset_line(Entitlement::to("rcc::ahb1rstr::crcrst::Asserted"))
 }
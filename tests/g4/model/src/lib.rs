@@ -1,6 +1,12 @@
-use proto_hal_build::ir::{
-    structures::{hal::Hal, interrupts::Interrupt},
-    utils::diagnostic::Diagnostics,
+use proto_hal_build::{
+    cortex_m,
+    ir::structures::{
+        cpu::{Core, Cpu},
+        entitlement::Entitlement,
+        hal::Hal,
+        interrupts::Interrupt,
+        memory::Region,
+    },
 };
 
 pub mod cordic;
@@ -15,7 +21,7 @@ pub enum DeviceVariant {
     G484,
 }
 
-pub fn generate(variant: DeviceVariant) -> (Hal, Diagnostics) {
+pub fn generate(variant: DeviceVariant) -> Hal {
     let extra_interrupts = |interrupt| {
         if matches!(variant, DeviceVariant::G474 | DeviceVariant::G484) {
             interrupt
@@ -24,112 +30,135 @@ pub fn generate(variant: DeviceVariant) -> (Hal, Diagnostics) {
         }
     };
 
-    let hal = Hal::new([rcc::generate(), cordic::generate(), crc::generate()]).interrupts([
-        Interrupt::handler("WWDG").docs(["Window Watchdog"]),
-        Interrupt::handler("PVD_PVM").docs(["PVD through EXTI line detection"]),
-        Interrupt::handler("RTC_TAMP_CSS_LSE"),
-        Interrupt::handler("RTC_WKUP").docs(["RTC Wakeup timer"]),
-        Interrupt::handler("FLASH"),
-        Interrupt::handler("RCC"),
-        Interrupt::handler("EXTI0"),
-        Interrupt::handler("EXTI1"),
-        Interrupt::handler("EXTI2"),
-        Interrupt::handler("EXTI3"),
-        Interrupt::handler("EXTI4"),
-        Interrupt::handler("DAM1_CH1"),
-        Interrupt::handler("DAM1_CH2"),
-        Interrupt::handler("DAM1_CH3"),
-        Interrupt::handler("DAM1_CH4"),
-        Interrupt::handler("DAM1_CH5"),
-        Interrupt::handler("DAM1_CH6"),
-        extra_interrupts(Interrupt::handler("DAM1_CH7")),
-        Interrupt::handler("ADC1_2").docs(["ADC1 and ADC2 global interrupt"]),
-        Interrupt::handler("USB_HP"),
-        Interrupt::handler("USB_LP"),
-        Interrupt::handler("FDCAN1_INTR0_IT"),
-        Interrupt::handler("FDCAN1_INTR1_IT"),
-        Interrupt::handler("EXTI9_5").docs(["EXTI lanes 5 through 9"]),
-        Interrupt::handler("TIM1_BRK_TIM15"),
-        Interrupt::handler("TIM1_UP_TIM16"),
-        Interrupt::handler("TIM1_TRG_COM"),
-        Interrupt::handler("TIM1_CC"),
-        Interrupt::handler("TIM2"),
-        Interrupt::handler("TIM3"),
-        Interrupt::handler("TIM4"),
-        Interrupt::handler("I2C1_EV"),
-        Interrupt::handler("I2C1_ER"),
-        Interrupt::handler("I2C2_EV"),
-        Interrupt::handler("I2C2_ER"),
-        Interrupt::handler("SPI1"),
-        Interrupt::handler("SPI2"),
-        Interrupt::handler("USART1"),
-        Interrupt::handler("USART2"),
-        Interrupt::handler("USART3"),
-        Interrupt::handler("EXTI15_10").docs(["EXTI lanes 10 through 15"]),
-        Interrupt::handler("RTC_ALARM"),
-        Interrupt::handler("USBWAKE_UP"),
-        Interrupt::handler("TIM8_BRK"),
-        Interrupt::handler("TIM8_UP"),
-        Interrupt::handler("TIM8_TRG_COM"),
-        Interrupt::handler("TIM8_CC"),
-        extra_interrupts(Interrupt::handler("ADC3")),
-        extra_interrupts(Interrupt::handler("FMC")),
-        Interrupt::handler("LPTIM1"),
-        extra_interrupts(Interrupt::handler("TIM5")),
-        Interrupt::handler("SPI3"),
-        Interrupt::handler("UART4"),
-        extra_interrupts(Interrupt::handler("UART5")),
-        Interrupt::handler("TIM6_DACUNDER"),
-        Interrupt::handler("TIM7"),
-        Interrupt::handler("DMA2_CH1"),
-        Interrupt::handler("DMA2_CH2"),
-        Interrupt::handler("DMA2_CH3"),
-        Interrupt::handler("DMA2_CH4"),
-        Interrupt::handler("DMA2_CH5"),
-        extra_interrupts(Interrupt::handler("ADC4")),
-        extra_interrupts(Interrupt::handler("ADC5")),
-        Interrupt::handler("UCPD1"),
-        Interrupt::handler("COMP1_2_3"),
-        Interrupt::handler("COMP4_5_6"),
-        extra_interrupts(Interrupt::handler("COMP7")),
-        extra_interrupts(Interrupt::handler("HRTIM_MASTER_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIMA_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIMB_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIMC_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIMD_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIME_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIM_FLT_IRQN")),
-        extra_interrupts(Interrupt::handler("HRTIM_TIMF_IRQN")),
-        Interrupt::handler("CRS"),
-        Interrupt::handler("SAI"),
-        extra_interrupts(Interrupt::handler("TIM20_BRK")),
-        extra_interrupts(Interrupt::handler("TIM20_UP")),
-        extra_interrupts(Interrupt::handler("TIM20_TRG_COM")),
-        extra_interrupts(Interrupt::handler("TIM20_CC")),
-        Interrupt::handler("FPU"),
-        extra_interrupts(Interrupt::handler("I2C4_EV")),
-        extra_interrupts(Interrupt::handler("I2C4_ER")),
-        extra_interrupts(Interrupt::handler("SPI4")),
-        Interrupt::handler("AES"),
-        extra_interrupts(Interrupt::handler("FDCAN2_INTR0")),
-        extra_interrupts(Interrupt::handler("FDCAN2_INTR1")),
-        extra_interrupts(Interrupt::handler("FDCAN3_INTR0")),
-        extra_interrupts(Interrupt::handler("FDCAN3_INTR1")),
-        Interrupt::handler("RNG"),
-        Interrupt::handler("LPUART"),
-        Interrupt::handler("I2C3_EV"),
-        Interrupt::handler("I2C3_ER"),
-        Interrupt::handler("DMAMUX_OVR"),
-        extra_interrupts(Interrupt::handler("QUADSPI")),
-        extra_interrupts(Interrupt::handler("DMA1_CH8")),
-        Interrupt::handler("DMA2_CH6"),
-        extra_interrupts(Interrupt::handler("DMA2_CH7")),
-        extra_interrupts(Interrupt::handler("DMA2_CH8")),
-        Interrupt::handler("CORDIC"),
-        Interrupt::handler("FMAC"),
-    ]);
+    let hal = Hal::new([rcc::generate(), cordic::generate(), crc::generate()])
+        .interrupts([
+            Interrupt::handler("WWDG").docs(["Window Watchdog"]),
+            Interrupt::handler("PVD_PVM").docs(["PVD through EXTI line detection"]),
+            Interrupt::handler("RTC_TAMP_CSS_LSE"),
+            Interrupt::handler("RTC_WKUP").docs(["RTC Wakeup timer"]),
+            Interrupt::handler("FLASH"),
+            Interrupt::handler("RCC"),
+            Interrupt::handler("EXTI0"),
+            Interrupt::handler("EXTI1"),
+            Interrupt::handler("EXTI2"),
+            Interrupt::handler("EXTI3"),
+            Interrupt::handler("EXTI4"),
+            Interrupt::handler("DMA1_CH1"),
+            Interrupt::handler("DMA1_CH2"),
+            Interrupt::handler("DMA1_CH3"),
+            Interrupt::handler("DMA1_CH4"),
+            Interrupt::handler("DMA1_CH5"),
+            Interrupt::handler("DMA1_CH6"),
+            extra_interrupts(Interrupt::handler("DMA1_CH7")),
+            Interrupt::handler("ADC1_2").docs(["ADC1 and ADC2 global interrupt"]),
+            Interrupt::handler("USB_HP"),
+            Interrupt::handler("USB_LP"),
+            Interrupt::handler("FDCAN1_INTR0_IT"),
+            Interrupt::handler("FDCAN1_INTR1_IT"),
+            Interrupt::handler("EXTI9_5").docs(["EXTI lanes 5 through 9"]),
+            Interrupt::handler("TIM1_BRK_TIM15"),
+            Interrupt::handler("TIM1_UP_TIM16"),
+            Interrupt::handler("TIM1_TRG_COM"),
+            Interrupt::handler("TIM1_CC"),
+            Interrupt::handler("TIM2"),
+            Interrupt::handler("TIM3"),
+            Interrupt::handler("TIM4"),
+            Interrupt::handler("I2C1_EV"),
+            Interrupt::handler("I2C1_ER"),
+            Interrupt::handler("I2C2_EV"),
+            Interrupt::handler("I2C2_ER"),
+            Interrupt::handler("SPI1"),
+            Interrupt::handler("SPI2"),
+            Interrupt::handler("USART1"),
+            Interrupt::handler("USART2"),
+            Interrupt::handler("USART3"),
+            Interrupt::handler("EXTI15_10").docs(["EXTI lanes 10 through 15"]),
+            Interrupt::handler("RTC_ALARM"),
+            Interrupt::handler("USBWAKE_UP"),
+            Interrupt::handler("TIM8_BRK"),
+            Interrupt::handler("TIM8_UP"),
+            Interrupt::handler("TIM8_TRG_COM"),
+            Interrupt::handler("TIM8_CC"),
+            extra_interrupts(Interrupt::handler("ADC3")),
+            extra_interrupts(Interrupt::handler("FMC")),
+            Interrupt::handler("LPTIM1"),
+            extra_interrupts(Interrupt::handler("TIM5")),
+            Interrupt::handler("SPI3"),
+            Interrupt::handler("UART4"),
+            extra_interrupts(Interrupt::handler("UART5")),
+            Interrupt::handler("TIM6_DACUNDER"),
+            Interrupt::handler("TIM7"),
+            Interrupt::handler("DMA2_CH1"),
+            Interrupt::handler("DMA2_CH2"),
+            Interrupt::handler("DMA2_CH3"),
+            Interrupt::handler("DMA2_CH4"),
+            Interrupt::handler("DMA2_CH5"),
+            extra_interrupts(Interrupt::handler("ADC4")),
+            extra_interrupts(Interrupt::handler("ADC5")),
+            Interrupt::handler("UCPD1"),
+            Interrupt::handler("COMP1_2_3"),
+            Interrupt::handler("COMP4_5_6"),
+            extra_interrupts(Interrupt::handler("COMP7")),
+            extra_interrupts(Interrupt::handler("HRTIM_MASTER_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIMA_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIMB_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIMC_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIMD_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIME_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIM_FLT_IRQN")),
+            extra_interrupts(Interrupt::handler("HRTIM_TIMF_IRQN")),
+            Interrupt::handler("CRS"),
+            Interrupt::handler("SAI"),
+            extra_interrupts(Interrupt::handler("TIM20_BRK")),
+            extra_interrupts(Interrupt::handler("TIM20_UP")),
+            extra_interrupts(Interrupt::handler("TIM20_TRG_COM")),
+            extra_interrupts(Interrupt::handler("TIM20_CC")),
+            Interrupt::handler("FPU"),
+            extra_interrupts(Interrupt::handler("I2C4_EV")),
+            extra_interrupts(Interrupt::handler("I2C4_ER")),
+            extra_interrupts(Interrupt::handler("SPI4")),
+            Interrupt::handler("AES"),
+            extra_interrupts(Interrupt::handler("FDCAN2_INTR0")),
+            extra_interrupts(Interrupt::handler("FDCAN2_INTR1")),
+            extra_interrupts(Interrupt::handler("FDCAN3_INTR0")),
+            extra_interrupts(Interrupt::handler("FDCAN3_INTR1")),
+            Interrupt::handler("RNG"),
+            Interrupt::handler("LPUART"),
+            Interrupt::handler("I2C3_EV"),
+            Interrupt::handler("I2C3_ER"),
+            Interrupt::handler("DMAMUX_OVR"),
+            extra_interrupts(Interrupt::handler("QUADSPI")),
+            extra_interrupts(Interrupt::handler("DMA1_CH8")),
+            Interrupt::handler("DMA2_CH6"),
+            extra_interrupts(Interrupt::handler("DMA2_CH7")),
+            extra_interrupts(Interrupt::handler("DMA2_CH8")),
+            Interrupt::handler("CORDIC")
+                .sources(["cordic"])
+                .enable(Entitlement::to("cordic::csr::ien::Enabled"))
+                .status(Entitlement::to("cordic::csr::rrdy::Ready")),
+            Interrupt::handler("FMAC"),
+        ])
+        .cpu(Cpu::new(Core::M4, 4).fpu().mpu())
+        .memory(memory(&variant));
 
-    let diagnostics = hal.validate();
+    cortex_m::merge(hal)
+}
+
+/// The memory map of the device variant.
+///
+/// *Note: SRAM1 and SRAM2 are contiguous, so they are described as a single region.*
+fn memory(variant: &DeviceVariant) -> [Region; 4] {
+    const K: u32 = 1024;
+
+    let (flash, ram, ccm) = match variant {
+        DeviceVariant::G431 | DeviceVariant::G441 => (128 * K, 22 * K, 10 * K),
+        DeviceVariant::G474 | DeviceVariant::G484 => (512 * K, 96 * K, 32 * K),
+    };
 
-    (hal, diagnostics)
+    [
+        Region::flash("flash", 0x0800_0000, flash).docs(["Main flash memory."]),
+        Region::ram("ram", 0x2000_0000, ram).docs(["SRAM1 and SRAM2."]),
+        Region::ram("ccmram", 0x1000_0000, ccm).docs(["Core coupled memory SRAM."]),
+        Region::read_only("option_bytes", 0x1fff_7800, 0x30).docs(["User option bytes."]),
+    ]
 }
@@ -1,4 +1,5 @@
 use g4_model::DeviceVariant;
+use proto_hal_build::{codegen::Config, ir::lint::Lint};
 
 fn main() {
     env_logger::init();
@@ -9,6 +10,9 @@ fn main() {
         DeviceVariant::G484,
     ] {
         println!("=== Variant: {variant:?} ===");
-        proto_hal_build::codegen::validate(|| g4_model::generate(variant));
+        proto_hal_build::codegen::validate_with(
+            || g4_model::generate(variant),
+            Config::new().lints(Lint::all()),
+        );
     }
 }
@@ -1,3 +1,4 @@
+pub mod ahb1rstr;
 pub mod ahbenr;
 
 use proto_hal_build::ir::structures::peripheral::Peripheral;
@@ -7,6 +8,7 @@ pub fn generate() -> Peripheral {
         "rcc",
         0x4002_1000,
         [
+            ahb1rstr::generate(),
             ahbenr::generate(ahbenr::Instance::I1),
             ahbenr::generate(ahbenr::Instance::I2),
         ],
@@ -0,0 +1,20 @@
+pub mod rst;
+
+use proto_hal_build::ir::structures::register::Register;
+
+pub fn generate() -> Register {
+    Register::new(
+        "ahb1rstr",
+        0x28,
+        [
+            rst::generate("dma1rst", 0),
+            rst::generate("dma2rst", 1),
+            rst::generate("dmamux1rst", 2),
+            rst::generate("cordicrst", 3),
+            rst::generate("fmacrst", 4),
+            rst::generate("flashrst", 8),
+            rst::generate("crcrst", 12),
+        ],
+    )
+    .reset(0)
+}
@@ -0,0 +1,19 @@
+use proto_hal_build::ir::{
+    access::Access,
+    structures::{
+        field::{Field, Numericity},
+        variant::Variant,
+    },
+};
+
+pub fn generate(ident: impl AsRef<str>, offset: u8) -> Field {
+    Field::new(
+        ident,
+        offset,
+        1,
+        Access::read_write(Numericity::enumerated([
+            Variant::new("Released", 0),
+            Variant::new("Asserted", 1),
+        ])),
+    )
+}
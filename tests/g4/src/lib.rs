@@ -6,11 +6,19 @@ include!(concat!(env!("OUT_DIR"), "/hal.rs"));
 mod tests {
     extern crate std;
 
-    static mut MOCK_RCC: [u32; 40] = [0; 40];
-
-    #[unsafe(export_name = "__PROTO_HAL_ADDR_OF_RCC")]
-    fn addr_of_rcc() -> usize {
-        (&raw const MOCK_RCC).addr()
+    mod hal {
+        /// Take the peripherals of a HAL declared to be in reset on startup.
+        ///
+        /// Expected behavior: The peripherals are taken without declaring so at runtime, and only
+        /// once.
+        #[test]
+        fn take() {
+            assert!(crate::Peripherals::take().is_ok());
+            assert!(matches!(
+                crate::Peripherals::take(),
+                Err(proto_hal::TakeError::Taken)
+            ));
+        }
     }
 
     mod cordic {
@@ -18,16 +26,11 @@ mod tests {
 
         use crate::{cordic, rcc};
 
-        static mut MOCK_CORDIC: [u32; 3] = [0x0000_0050, 0, 0];
-
-        #[unsafe(export_name = "__PROTO_HAL_ADDR_OF_CORDIC")]
-        fn addr_of_cordic() -> usize {
-            (&raw const MOCK_CORDIC).addr()
-        }
-
         #[test]
         fn basic() {
             critical_section::with(|cs| {
+                crate::reset_mocks();
+
                 let p = unsafe { crate::peripherals() };
 
                 let rcc::ahb1enr::States { cordicen, .. } =
@@ -59,9 +62,26 @@ mod tests {
             });
         }
 
+        #[test]
+        fn mock_reset() {
+            critical_section::with(|_| {
+                unsafe { cordic::MOCK[0] = u32::MAX };
+
+                crate::reset_mocks();
+
+                assert!({
+                    let csr = unsafe { cordic::csr::read_untracked() };
+
+                    csr.func().is_cos() && csr.scale().is_n0() && csr.precision().is_p20()
+                });
+            });
+        }
+
         #[test]
         fn wdata() {
             critical_section::with(|cs| {
+                crate::reset_mocks();
+
                 let p = unsafe { crate::peripherals() };
 
                 let rcc::ahb1enr::States { cordicen, .. } =
@@ -74,14 +94,16 @@ mod tests {
 
                 cordic::wdata::write(|w| w.arg(&mut arg, 0xdeadbeefu32));
 
-                assert_eq!(unsafe { MOCK_CORDIC }[1], 0xdeadbeef);
+                assert_eq!(unsafe { cordic::MOCK }[1], 0xdeadbeef);
             });
         }
 
         #[test]
         fn rdata() {
             critical_section::with(|cs| {
-                unsafe { MOCK_CORDIC[2] = 0xdeadbeef };
+                crate::reset_mocks();
+
+                unsafe { cordic::MOCK[2] = 0xdeadbeef };
 
                 let p = unsafe { crate::peripherals() };
 
@@ -112,16 +134,11 @@ mod tests {
     mod crc {
         use crate::{crc, rcc};
 
-        static mut MOCK_CRC: [u32; 2] = [0, 0];
-
-        #[unsafe(export_name = "__PROTO_HAL_ADDR_OF_CRC")]
-        fn addr_of_crc() -> usize {
-            (&raw const MOCK_CRC).addr()
-        }
-
         #[test]
         fn basic() {
             critical_section::with(|cs| {
+                crate::reset_mocks();
+
                 let p = unsafe { crate::peripherals() };
 
                 let rcc::ahb1enr::States { crcen, .. } =
@@ -131,13 +148,15 @@ mod tests {
                 let crc::idr::States { idr } =
                     crc::idr::write(|w| w.idr(crc.idr.idr).value::<0xdeadbeef>());
 
-                assert_eq!(idr.value(), unsafe { MOCK_CRC[1] });
+                assert_eq!(idr.value(), unsafe { crc::MOCK[1] });
             });
         }
 
         #[test]
         fn inert() {
             critical_section::with(|cs| {
+                crate::reset_mocks();
+
                 let p = unsafe { crate::peripherals() };
 
                 let rcc::ahb1enr::States { crcen, .. } =
@@ -155,6 +174,245 @@ mod tests {
         }
     }
 
+    mod reset_line {
+        use core::any::{Any, TypeId};
+
+        use crate::{crc, rcc};
+
+        /// Pulse the reset line of a peripheral.
+        ///
+        /// Expected behavior: The field states of the peripheral revert to their reset states.
+        #[test]
+        fn pulse() {
+            critical_section::with(|cs| {
+                crate::reset_mocks();
+
+                let p = unsafe { crate::peripherals() };
+
+                let rcc::ahb1enr::States { crcen, .. } =
+                    rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(p.rcc.ahb1enr.crcen).enabled());
+                let crc = p.crc.unmask(crcen);
+
+                let cr = crc::cr::write(|w| {
+                    w.polysize(crc.cr.polysize)
+                        .p8()
+                        .rev_in(crc.cr.rev_in)
+                        .preserve()
+                });
+                let idr = crc::idr::write(|w| w.idr(crc.idr.idr).value::<0xdeadbeef>());
+
+                let rcc::ahb1rstr::States { crcrst, .. } =
+                    rcc::ahb1rstr::modify_in_cs(cs, |_, w| {
+                        w.crcrst(p.rcc.ahb1rstr.crcrst).asserted()
+                    });
+
+                let crc = crc::reset_peripheral(
+                    crc.entitlements,
+                    crc.dr,
+                    crc::idr::Fields { idr: idr.idr },
+                    crc::cr::Fields {
+                        rst: crc.cr.rst,
+                        polysize: cr.polysize,
+                        rev_in: cr.rev_in,
+                    },
+                    &crcrst,
+                );
+
+                rcc::ahb1rstr::modify_in_cs(cs, |_, w| w.crcrst(crcrst).released());
+
+                assert_eq!(
+                    crc.cr.polysize.type_id(),
+                    TypeId::of::<crc::cr::polysize::P32>()
+                );
+                assert_eq!(
+                    crc.idr.idr.type_id(),
+                    TypeId::of::<crc::idr::idr::Value<0>>()
+                );
+            });
+        }
+    }
+
+    mod mask {
+        use proto_hal::stasis::Freeze;
+
+        use crate::{crc, rcc};
+
+        /// Mask an unmasked peripheral to power it down.
+        ///
+        /// Expected behavior: The released entitlement unfreezes the clock-enable state, which
+        /// can then be transitioned again.
+        #[test]
+        fn release() {
+            critical_section::with(|cs| {
+                crate::reset_mocks();
+
+                let p = unsafe { crate::peripherals() };
+
+                let rcc::ahb1enr::States { crcen, .. } =
+                    rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(p.rcc.ahb1enr.crcen).enabled());
+                let (crcen, [entitlement]) = crcen.freeze();
+
+                let crc = p.crc.unmask(entitlement);
+
+                let (_, entitlement) = crc.mask();
+                let crcen = crcen.release([entitlement]);
+
+                rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(crcen).disabled());
+
+                assert!(
+                    unsafe { rcc::ahb1enr::read_untracked() }
+                        .crcen()
+                        .is_disabled()
+                );
+            });
+        }
+
+        /// Mask a peripheral from the states of its registers, once they have been moved out.
+        ///
+        /// Expected behavior: The register states are restored to reset and the released
+        /// entitlement unfreezes the clock-enable state.
+        #[test]
+        fn states() {
+            critical_section::with(|cs| {
+                crate::reset_mocks();
+
+                let p = unsafe { crate::peripherals() };
+
+                let rcc::ahb1enr::States { crcen, .. } =
+                    rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(p.rcc.ahb1enr.crcen).enabled());
+                let (crcen, [entitlement]) = crcen.freeze();
+
+                let crc = p.crc.unmask(entitlement);
+
+                let cr = crc::cr::write(|w| {
+                    w.polysize(crc.cr.polysize)
+                        .p8()
+                        .rev_in(crc.cr.rev_in)
+                        .preserve()
+                });
+
+                assert!(unsafe { crc::cr::read_untracked() }.polysize().is_p8());
+
+                let (_, entitlement) = crc::mask(
+                    crc.entitlements,
+                    crc.dr,
+                    crc.idr,
+                    crc::cr::Fields {
+                        rst: crc.cr.rst,
+                        polysize: cr.polysize,
+                        rev_in: cr.rev_in,
+                    },
+                );
+                let crcen = crcen.release([entitlement]);
+
+                rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(crcen).disabled());
+
+                assert!(unsafe { crc::cr::read_untracked() }.polysize().is_p32());
+            });
+        }
+    }
+
+    mod clock_gate {
+        use proto_hal::stasis::Freeze;
+
+        use crate::{crc, rcc};
+
+        /// Gate the clock of an unmasked peripheral.
+        ///
+        /// Expected behavior: The released entitlement allows the clock to be gated, after which
+        /// the peripheral is masked without its registers being written.
+        #[test]
+        fn gate() {
+            critical_section::with(|cs| {
+                crate::reset_mocks();
+
+                let p = unsafe { crate::peripherals() };
+
+                let rcc::ahb1enr::States { crcen, .. } =
+                    rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(p.rcc.ahb1enr.crcen).enabled());
+                let (crcen, [entitlement]) = crcen.freeze();
+
+                let crc = p.crc.unmask(entitlement);
+
+                let cr = crc::cr::write(|w| {
+                    w.polysize(crc.cr.polysize)
+                        .p8()
+                        .rev_in(crc.cr.rev_in)
+                        .preserve()
+                });
+
+                let (gating, entitlement) = crc::gate_clock(
+                    crc.entitlements,
+                    crc.dr,
+                    crc.idr,
+                    crc::cr::Fields {
+                        rst: crc.cr.rst,
+                        polysize: cr.polysize,
+                        rev_in: cr.rev_in,
+                    },
+                );
+                let crcen = crcen.release([entitlement]);
+
+                let rcc::ahb1enr::States { crcen, .. } =
+                    rcc::ahb1enr::modify_in_cs(cs, |_, w| w.crcen(crcen).disabled());
+
+                let _crc: crc::Masked = gating.gated(&crcen);
+
+                assert!(unsafe { crc::cr::read_untracked() }.polysize().is_p8());
+            });
+        }
+    }
+
+    mod core_peripherals {
+        use crate::{dcb, dwt, nvic};
+
+        /// Enable trace to access the cycle counter of the merged core peripherals.
+        ///
+        /// Expected behavior: The DWT is unmasked by the trace enable state and its
+        /// counter is written to the DWT registers.
+        #[test]
+        fn trace() {
+            critical_section::with(|cs| {
+                crate::reset_mocks();
+
+                let p = unsafe { crate::peripherals() };
+
+                let dcb::demcr::States { trcena } =
+                    dcb::demcr::modify_in_cs(cs, |_, w| w.trcena(p.dcb.demcr.trcena).enabled());
+                let mut dwt = p.dwt.unmask(trcena);
+
+                dwt::cyccnt::write(|w| w.cyccnt(&mut dwt.cyccnt.cyccnt, 0xdeadbeefu32));
+
+                assert_eq!(unsafe { dwt::MOCK }[1], 0xdeadbeef);
+            });
+        }
+
+        /// Inspect the NVIC registers sized from the interrupt table.
+        ///
+        /// Expected behavior: The last interrupt (FMAC at position 101) is covered by the
+        /// fourth enable register, which compiles.
+        #[test]
+        fn nvic_sized() {
+            let p = unsafe { crate::peripherals() };
+
+            let _: nvic::iser3::setena101::Dynamic = p.nvic.iser3.setena101;
+        }
+    }
+
+    mod memory {
+        use crate::memory;
+
+        /// Inspect the memory map of the default device variant (G484).
+        ///
+        /// Expected behavior: The regions are sized for the variant.
+        #[test]
+        fn regions() {
+            assert_eq!(memory::FLASH.length, 512 * 1024);
+            assert_eq!(memory::RAM.end(), 0x2001_8000);
+            assert!(memory::CCMRAM.contains(0x1000_7fff));
+        }
+    }
+
     mod rcc {
         use core::any::{Any, TypeId};
 